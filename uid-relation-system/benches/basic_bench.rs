@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use uid_relation_system::core::types::{UID, RelationalPosition, Direction};
+use uid_relation_system::core::types::{UID, RelationalPosition, Direction, ErrorLevel, SystemError};
+use uid_relation_system::error::{make_error_sequence, make_error_sequence_into};
 
 fn bench_uid_creation(c: &mut Criterion) {
     c.bench_function("uid_creation", |b| {
@@ -26,5 +27,48 @@ fn bench_position_creation(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_uid_creation, bench_position_creation);
+fn bench_error_sequence_alloc(c: &mut Criterion) {
+    // 每次调用都新分配一个 Vec
+    c.bench_function("error_sequence_alloc", |b| {
+        b.iter(|| {
+            for _ in 0..1000 {
+                let seq = make_error_sequence(
+                    black_box(ErrorLevel::Warning),
+                    black_box(SystemError::InvalidPosition),
+                    Vec::new(),
+                    Vec::new(),
+                );
+                black_box(seq);
+            }
+        })
+    });
+}
+
+fn bench_error_sequence_into(c: &mut Criterion) {
+    // 复用同一缓冲区，避免每次分配
+    c.bench_function("error_sequence_into", |b| {
+        let mut buf = Vec::with_capacity(8);
+        b.iter(|| {
+            for _ in 0..1000 {
+                buf.clear();
+                make_error_sequence_into(
+                    &mut buf,
+                    black_box(ErrorLevel::Warning),
+                    black_box(SystemError::InvalidPosition),
+                    &[],
+                    &[],
+                );
+                black_box(&buf);
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_uid_creation,
+    bench_position_creation,
+    bench_error_sequence_alloc,
+    bench_error_sequence_into
+);
 criterion_main!(benches);
\ No newline at end of file
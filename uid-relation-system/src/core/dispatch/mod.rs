@@ -0,0 +1,140 @@
+//! 操作码分发引擎
+//!
+//! 将 `OP_*` 系统UID映射到已注册的处理器，类比内核的系统调用分发表。
+//! 每个处理器签名为 `Fn(&[UID]) -> Result<UID, UID>`：接收参数UID序列，
+//! 返回成功结果UID或错误标记UID。内置的 `OP_*` 操作码在系统初始化时预注册
+//! 为空操作桩，供下游代码替换为真正的行为。
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use lazy_static::lazy_static;
+use crate::core::types::UID;
+
+/// 操作码处理器类型
+pub type OpHandler = Box<dyn Fn(&[UID]) -> Result<UID, UID> + Send + Sync>;
+
+/// 操作码分发器
+pub struct OpDispatcher {
+    handlers: RwLock<HashMap<UID, OpHandler>>,
+}
+
+impl OpDispatcher {
+    pub fn new() -> Self {
+        Self {
+            handlers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 注册（或覆盖）某个操作码的处理器
+    pub fn register_op(&self, uid: UID, handler: OpHandler) {
+        self.handlers.write().unwrap().insert(uid, handler);
+    }
+
+    /// 查询并调用操作码对应的处理器
+    ///
+    /// 未知操作码返回 `Err(error_info())`。
+    pub fn dispatch(&self, opcode: UID, args: &[UID]) -> Result<UID, UID> {
+        let handlers = self.handlers.read().unwrap();
+        match handlers.get(&opcode) {
+            Some(handler) => handler(args),
+            None => Err(crate::core::system_uids::error_info()),
+        }
+    }
+
+    /// 是否已注册某个操作码
+    pub fn has_op(&self, opcode: UID) -> bool {
+        self.handlers.read().unwrap().contains_key(&opcode)
+    }
+}
+
+impl Default for OpDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 为内置的 `OP_*` 操作码预注册空操作桩
+///
+/// 桩处理器一律返回 `success_marker()`，保持操作码可分发直到被真正的实现替换。
+/// 必须在系统符号注册之后调用（此时 `op_*()` 可解析为有效UID）。
+pub fn register_builtin_ops(dispatcher: &OpDispatcher) {
+    use crate::core::system_uids::{
+        op_copy, op_delete, op_execute, op_insert, op_move, op_relate, op_search, success_marker,
+    };
+
+    let builtins = [
+        op_move(),
+        op_insert(),
+        op_delete(),
+        op_copy(),
+        op_relate(),
+        op_search(),
+        op_execute(),
+    ];
+
+    for opcode in builtins {
+        dispatcher.register_op(opcode, Box::new(|_args: &[UID]| Ok(success_marker())));
+    }
+}
+
+// 全局操作码分发器实例
+lazy_static! {
+    static ref GLOBAL_DISPATCHER: OpDispatcher = OpDispatcher::new();
+}
+
+/// 获取全局操作码分发器
+pub fn global_dispatcher() -> &'static OpDispatcher {
+    &GLOBAL_DISPATCHER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::system_uids;
+
+    #[test]
+    fn test_builtin_stub_dispatch() {
+        system_uids::initialize_system_uids().unwrap();
+
+        let dispatcher = OpDispatcher::new();
+        register_builtin_ops(&dispatcher);
+
+        // 内置操作码应分发到桩并返回成功标记
+        let result = dispatcher.dispatch(system_uids::op_move(), &[UID(1), UID(2)]);
+        assert_eq!(result, Ok(system_uids::success_marker()));
+    }
+
+    #[test]
+    fn test_unknown_opcode_returns_error_info() {
+        system_uids::initialize_system_uids().unwrap();
+
+        let dispatcher = OpDispatcher::new();
+        let result = dispatcher.dispatch(UID(0xDEADBEEF), &[]);
+        assert_eq!(result, Err(system_uids::error_info()));
+    }
+
+    #[test]
+    fn test_custom_handler() {
+        system_uids::initialize_system_uids().unwrap();
+
+        let dispatcher = OpDispatcher::new();
+        // 自定义处理器：返回第一个参数，或失败标记
+        dispatcher.register_op(
+            system_uids::op_search(),
+            Box::new(|args: &[UID]| {
+                args.first()
+                    .copied()
+                    .ok_or_else(system_uids::failure_marker)
+            }),
+        );
+
+        assert_eq!(
+            dispatcher.dispatch(system_uids::op_search(), &[UID(42)]),
+            Ok(UID(42))
+        );
+        assert_eq!(
+            dispatcher.dispatch(system_uids::op_search(), &[]),
+            Err(system_uids::failure_marker())
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! ERROR_* 严重级别标记的信号式订阅与投递
+//!
+//! 借鉴内核按目标投递信号的模型（挂起集合、掩码与通知）：订阅者按最低
+//! [`ErrorLevel`] 注册回调，`raise` 把事件扇出到匹配的订阅者。每个严重级别
+//! 维护一个有界挂起环，使在任何订阅者出现之前产生的事件能在首个订阅时被重放
+//! （如挂起信号）；掩码允许订阅者临时抑制低于某阈值的级别。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+use crate::core::types::{ErrorLevel, UID};
+
+/// 订阅句柄
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(pub u64);
+
+/// 订阅者回调类型
+pub type ErrorCallback = Box<dyn Fn(ErrorLevel, &[UID]) + Send + Sync>;
+
+/// 严重度排名：Fatal 最高
+fn severity_rank(level: ErrorLevel) -> u8 {
+    match level {
+        ErrorLevel::Fatal => 3,
+        ErrorLevel::Severe => 2,
+        ErrorLevel::Warning => 1,
+        ErrorLevel::Info => 0,
+    }
+}
+
+/// 将错误级别UID解码为 [`ErrorLevel`]
+pub fn level_from_uid(uid: UID) -> Option<ErrorLevel> {
+    use crate::core::system_uids::{error_fatal, error_info, error_severe, error_warning};
+    if uid == error_fatal() {
+        Some(ErrorLevel::Fatal)
+    } else if uid == error_severe() {
+        Some(ErrorLevel::Severe)
+    } else if uid == error_warning() {
+        Some(ErrorLevel::Warning)
+    } else if uid == error_info() {
+        Some(ErrorLevel::Info)
+    } else {
+        None
+    }
+}
+
+struct Subscriber {
+    id: SubscriptionId,
+    min_level: ErrorLevel,
+    /// 临时掩码：抑制低于此阈值的级别
+    mask: Option<ErrorLevel>,
+    callback: ErrorCallback,
+}
+
+impl Subscriber {
+    /// 事件是否应投递给该订阅者（取 min_level 与掩码中更严格者）
+    fn accepts(&self, level: ErrorLevel) -> bool {
+        let mut threshold = severity_rank(self.min_level);
+        if let Some(mask) = self.mask {
+            threshold = threshold.max(severity_rank(mask));
+        }
+        severity_rank(level) >= threshold
+    }
+}
+
+struct BusState {
+    subscribers: Vec<Subscriber>,
+    next_id: u64,
+    /// 每个严重级别的有界挂起环
+    pending: HashMap<ErrorLevel, VecDeque<Vec<UID>>>,
+}
+
+/// 错误事件总线
+pub struct ErrorBus {
+    inner: Mutex<BusState>,
+    ring_capacity: usize,
+}
+
+impl ErrorBus {
+    /// 创建总线，`ring_capacity` 为每个严重级别挂起环的容量
+    pub fn new(ring_capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(BusState {
+                subscribers: Vec::new(),
+                next_id: 0,
+                pending: HashMap::new(),
+            }),
+            ring_capacity,
+        }
+    }
+
+    /// 注册订阅者，按最低严重级别过滤
+    ///
+    /// 若这是首个订阅者，则把各级别挂起环中的事件按严重度从高到低重放给它。
+    pub fn subscribe(&self, min_level: ErrorLevel, callback: ErrorCallback) -> SubscriptionId {
+        let mut st = self.inner.lock().unwrap();
+        let id = SubscriptionId(st.next_id);
+        st.next_id += 1;
+
+        let subscriber = Subscriber {
+            id,
+            min_level,
+            mask: None,
+            callback,
+        };
+
+        let first_subscriber = st.subscribers.is_empty();
+        st.subscribers.push(subscriber);
+
+        if first_subscriber {
+            // 按严重度从高到低重放挂起事件
+            let levels = [
+                ErrorLevel::Fatal,
+                ErrorLevel::Severe,
+                ErrorLevel::Warning,
+                ErrorLevel::Info,
+            ];
+            let sub = st.subscribers.last().unwrap();
+            let mut replay: Vec<(ErrorLevel, Vec<UID>)> = Vec::new();
+            for level in levels {
+                if let Some(queue) = st.pending.get(&level) {
+                    for payload in queue {
+                        if sub.accepts(level) {
+                            replay.push((level, payload.clone()));
+                        }
+                    }
+                }
+            }
+            // 重放并清空挂起环
+            let callback = &st.subscribers.last().unwrap().callback;
+            for (level, payload) in &replay {
+                callback(*level, payload);
+            }
+            st.pending.clear();
+        }
+
+        id
+    }
+
+    /// 设置或清除订阅者的掩码
+    pub fn set_mask(&self, id: SubscriptionId, mask: Option<ErrorLevel>) -> bool {
+        let mut st = self.inner.lock().unwrap();
+        if let Some(sub) = st.subscribers.iter_mut().find(|s| s.id == id) {
+            sub.mask = mask;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 取消订阅
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut st = self.inner.lock().unwrap();
+        let before = st.subscribers.len();
+        st.subscribers.retain(|s| s.id != id);
+        st.subscribers.len() != before
+    }
+
+    /// 以级别UID触发事件，返回投递到的订阅者数量
+    ///
+    /// 未知级别UID被忽略（返回 0）。没有订阅者时压入对应严重级别的挂起环。
+    pub fn raise(&self, level_uid: UID, payload: &[UID]) -> usize {
+        match level_from_uid(level_uid) {
+            Some(level) => self.raise_level(level, payload),
+            None => 0,
+        }
+    }
+
+    /// 以 [`ErrorLevel`] 触发事件，返回投递到的订阅者数量
+    pub fn raise_level(&self, level: ErrorLevel, payload: &[UID]) -> usize {
+        let st = self.inner.lock().unwrap();
+
+        if st.subscribers.is_empty() {
+            // 无订阅者：压入有界挂起环，超限时丢弃最旧事件
+            drop(st);
+            let mut st = self.inner.lock().unwrap();
+            let cap = self.ring_capacity;
+            let queue = st.pending.entry(level).or_default();
+            queue.push_back(payload.to_vec());
+            while queue.len() > cap {
+                queue.pop_front();
+            }
+            return 0;
+        }
+
+        let mut delivered = 0;
+        for sub in &st.subscribers {
+            if sub.accepts(level) {
+                (sub.callback)(level, payload);
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+}
+
+// 全局错误总线实例（每级别默认保留64条挂起事件）
+lazy_static! {
+    static ref GLOBAL_ERROR_BUS: ErrorBus = ErrorBus::new(64);
+}
+
+/// 获取全局错误总线
+pub fn global_error_bus() -> &'static ErrorBus {
+    &GLOBAL_ERROR_BUS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_level_filtering() {
+        let bus = ErrorBus::new(8);
+        let hits = Arc::new(AtomicUsize::new(0));
+        let h = hits.clone();
+        // 只接收 Severe 及以上
+        bus.subscribe(
+            ErrorLevel::Severe,
+            Box::new(move |_lvl, _payload| {
+                h.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        assert_eq!(bus.raise_level(ErrorLevel::Fatal, &[UID(1)]), 1);
+        assert_eq!(bus.raise_level(ErrorLevel::Severe, &[]), 1);
+        // Warning 低于阈值，不投递
+        assert_eq!(bus.raise_level(ErrorLevel::Warning, &[]), 0);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_pending_replay_on_first_subscribe() {
+        let bus = ErrorBus::new(8);
+        // 尚无订阅者，事件进入挂起环
+        bus.raise_level(ErrorLevel::Fatal, &[UID(7)]);
+        bus.raise_level(ErrorLevel::Info, &[UID(8)]);
+
+        let received = Arc::new(AtomicUsize::new(0));
+        let r = received.clone();
+        bus.subscribe(
+            ErrorLevel::Info,
+            Box::new(move |_lvl, _payload| {
+                r.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        // 两条挂起事件都应在首次订阅时被重放
+        assert_eq!(received.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_mask_suppresses_levels() {
+        let bus = ErrorBus::new(8);
+        let hits = Arc::new(AtomicUsize::new(0));
+        let h = hits.clone();
+        let id = bus.subscribe(
+            ErrorLevel::Info,
+            Box::new(move |_lvl, _payload| {
+                h.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        // 掩码提升到 Fatal，只剩致命级别可投递
+        bus.set_mask(id, Some(ErrorLevel::Fatal));
+        assert_eq!(bus.raise_level(ErrorLevel::Warning, &[]), 0);
+        assert_eq!(bus.raise_level(ErrorLevel::Fatal, &[]), 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
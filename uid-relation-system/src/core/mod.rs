@@ -3,27 +3,46 @@
 //! 包含系统的基础类型和实现
 
 pub mod types;
+
+// 下列模块直接依赖 `std`（`std::fs`/`RwLock`/`HashMap` 等），仅在 `std` 特性下编译。
+// 关闭默认特性时只保留 `types` 与 `error` 子集，以支撑 no_std 错误序列机制。
+#[cfg(feature = "std")]
 pub mod uid_gen;
+#[cfg(feature = "std")]
 pub mod symbol_map;
+#[cfg(feature = "std")]
 pub mod system_uids;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "std")]
+pub mod dispatch;
+#[cfg(feature = "std")]
+pub mod error_bus;
+#[cfg(feature = "std")]
+pub mod registry;
 
 // 重新导出常用类型
 pub use types::*;
+#[cfg(feature = "std")]
 pub use uid_gen::*;
+#[cfg(feature = "std")]
 pub use symbol_map::*;
+#[cfg(feature = "std")]
 pub use system_uids::*;
 
 /// 初始化核心系统
 /// 这应该在应用程序启动时调用
+#[cfg(feature = "std")]
 pub fn initialize_core_system() -> Result<(), Box<dyn std::error::Error>> {
     // 初始化系统UID
     system_uids::initialize_system_uids()?;
-    
+
     log::info!("Core system initialized");
     Ok(())
 }
 
 /// 系统信息
+#[cfg(feature = "std")]
 pub fn system_info() -> SystemInfo {
     SystemInfo {
         version: crate::VERSION,
@@ -34,6 +53,7 @@ pub fn system_info() -> SystemInfo {
 }
 
 /// 系统信息结构
+#[cfg(feature = "std")]
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
     pub version: &'static str,
@@ -42,7 +62,7 @@ pub struct SystemInfo {
     pub system_uids_initialized: bool,
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     
@@ -74,8 +94,7 @@ mod tests {
         
         // 创建符号映射
         let symbol = "test_symbol";
-        let context = symbol_map::Context::Global;
-        
+
         // 注册符号
         let registered_uid = symbol_map::register_global_symbol(symbol).unwrap();
         println!("Registered UID for '{}': {}", symbol, registered_uid);
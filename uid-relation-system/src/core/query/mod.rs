@@ -0,0 +1,326 @@
+//! 符号/上下文查询DSL
+//!
+//! 在 `SymbolTable` 之上提供一个文本查询接口，让调用者无需手工拼装
+//! `Context` 即可表达诸如 `pi @domain:math`、`root @domain:*`、
+//! `apple | pomme` 之类的查询。
+//!
+//! 处理流程：源文本 -> 词法分析（`tokenize`）-> 递归下降解析（`parse`）
+//! -> `Query` AST -> `SymbolTable::run_query` 求值。
+
+use crate::core::symbol_map::Context;
+use crate::core::types::UID;
+
+/// 上下文选择器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextSelector {
+    /// `@global`
+    Global,
+    /// `@domain:NAME`
+    Domain(String),
+    /// `@domain:*`，枚举符号命中的所有领域上下文
+    DomainAny,
+    /// `@custom:UID`
+    Custom(UID),
+    /// `@temp`
+    Temp,
+}
+
+/// 词法单元
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Ident(String),
+    Context(ContextSelector),
+    Star,
+    Pipe,
+    LParen,
+    RParen,
+}
+
+/// 查询AST
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// 裸符号（可含glob通配），默认全局上下文
+    Symbol(String),
+    /// 将子查询约束到某个上下文选择器
+    InContext(Box<Query>, ContextSelector),
+    /// 并集
+    Or(Box<Query>, Box<Query>),
+    /// 括号分组
+    Group(Box<Query>),
+}
+
+/// 查询解析错误
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("非法字符 '{0}' 位于偏移 {1}")]
+    UnexpectedChar(char, usize),
+
+    #[error("非法上下文选择器: '{0}'")]
+    BadSelector(String),
+
+    #[error("意外的词法单元: {0:?}")]
+    UnexpectedToken(Token),
+
+    #[error("查询意外结束")]
+    UnexpectedEnd,
+}
+
+/// 将查询文本切分为词法单元序列
+pub fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '|' => {
+                tokens.push(Token::Pipe);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '*' => {
+                tokens.push(Token::Star);
+                i += 1;
+            }
+            '@' => {
+                // 读取到下一个分隔符为止
+                let start = i + 1;
+                let mut j = start;
+                while j < chars.len() && !is_delimiter(chars[j]) {
+                    j += 1;
+                }
+                let raw: String = chars[start..j].iter().collect();
+                tokens.push(Token::Context(parse_selector(&raw)?));
+                i = j;
+            }
+            c if is_ident_char(c) => {
+                let start = i;
+                let mut j = i;
+                while j < chars.len() && is_ident_char(chars[j]) {
+                    j += 1;
+                }
+                let ident: String = chars[start..j].iter().collect();
+                tokens.push(Token::Ident(ident));
+                i = j;
+            }
+            other => return Err(QueryError::UnexpectedChar(other, i)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// 是否为上下文选择器的分隔符
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '|' | '(' | ')')
+}
+
+/// 是否为标识符/glob可用字符
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '?' | '[' | ']' | '^' | '-')
+}
+
+/// 解析 `@` 之后的选择器字符串
+fn parse_selector(raw: &str) -> Result<ContextSelector, QueryError> {
+    match raw {
+        "global" => Ok(ContextSelector::Global),
+        "temp" => Ok(ContextSelector::Temp),
+        _ => {
+            if let Some(name) = raw.strip_prefix("domain:") {
+                if name == "*" {
+                    Ok(ContextSelector::DomainAny)
+                } else if name.is_empty() {
+                    Err(QueryError::BadSelector(raw.to_string()))
+                } else {
+                    Ok(ContextSelector::Domain(name.to_string()))
+                }
+            } else if let Some(uid) = raw.strip_prefix("custom:") {
+                parse_uid(uid)
+                    .map(ContextSelector::Custom)
+                    .ok_or_else(|| QueryError::BadSelector(raw.to_string()))
+            } else {
+                Err(QueryError::BadSelector(raw.to_string()))
+            }
+        }
+    }
+}
+
+/// 解析UID字面量（支持 `0x` 十六进制与十进制）
+fn parse_uid(s: &str) -> Option<UID> {
+    let value = if let Some(hex) = s.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()?
+    } else {
+        s.parse::<u64>().ok()?
+    };
+    Some(UID(value))
+}
+
+/// 将词法单元解析为 `Query` AST
+pub fn parse(tokens: &[Token]) -> Result<Query, QueryError> {
+    let mut parser = Parser { tokens, pos: 0 };
+    let query = parser.parse_or()?;
+    if parser.pos != tokens.len() {
+        return Err(QueryError::UnexpectedToken(tokens[parser.pos].clone()));
+    }
+    Ok(query)
+}
+
+/// 直接从源文本解析查询
+pub fn parse_query(input: &str) -> Result<Query, QueryError> {
+    let tokens = tokenize(input)?;
+    parse(&tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&Token> {
+        let tok = self.tokens.get(self.pos);
+        if tok.is_some() {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    /// or := term ('|' term)*
+    fn parse_or(&mut self) -> Result<Query, QueryError> {
+        let mut lhs = self.parse_term()?;
+        while matches!(self.peek(), Some(Token::Pipe)) {
+            self.bump();
+            let rhs = self.parse_term()?;
+            lhs = Query::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    /// term := primary context?
+    fn parse_term(&mut self) -> Result<Query, QueryError> {
+        let primary = self.parse_primary()?;
+        if let Some(Token::Context(sel)) = self.peek() {
+            let sel = sel.clone();
+            self.bump();
+            Ok(Query::InContext(Box::new(primary), sel))
+        } else {
+            Ok(primary)
+        }
+    }
+
+    /// primary := Ident | Star | '(' or ')'
+    fn parse_primary(&mut self) -> Result<Query, QueryError> {
+        match self.bump() {
+            Some(Token::Ident(s)) => Ok(Query::Symbol(s.clone())),
+            Some(Token::Star) => Ok(Query::Symbol("*".to_string())),
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.bump() {
+                    Some(Token::RParen) => Ok(Query::Group(Box::new(inner))),
+                    Some(other) => Err(QueryError::UnexpectedToken(other.clone())),
+                    None => Err(QueryError::UnexpectedEnd),
+                }
+            }
+            Some(other) => Err(QueryError::UnexpectedToken(other.clone())),
+            None => Err(QueryError::UnexpectedEnd),
+        }
+    }
+}
+
+/// 判断选择器是否匹配某个具体上下文
+pub(crate) fn selector_matches(selector: &ContextSelector, context: &Context) -> bool {
+    match (selector, context) {
+        (ContextSelector::Global, Context::Global) => true,
+        (ContextSelector::Temp, Context::Temporary) => true,
+        (ContextSelector::Domain(a), Context::Domain(b)) => a == b,
+        (ContextSelector::DomainAny, Context::Domain(_)) => true,
+        (ContextSelector::Custom(a), Context::Custom(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_basic() {
+        let tokens = tokenize("pi @domain:math").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("pi".to_string()),
+                Token::Context(ContextSelector::Domain("math".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_selectors() {
+        assert_eq!(
+            tokenize("@global").unwrap(),
+            vec![Token::Context(ContextSelector::Global)]
+        );
+        assert_eq!(
+            tokenize("@domain:*").unwrap(),
+            vec![Token::Context(ContextSelector::DomainAny)]
+        );
+        assert_eq!(
+            tokenize("@custom:0x10").unwrap(),
+            vec![Token::Context(ContextSelector::Custom(UID(0x10)))]
+        );
+        assert_eq!(
+            tokenize("@temp").unwrap(),
+            vec![Token::Context(ContextSelector::Temp)]
+        );
+    }
+
+    #[test]
+    fn test_parse_in_context() {
+        let q = parse_query("pi @domain:math").unwrap();
+        assert_eq!(
+            q,
+            Query::InContext(
+                Box::new(Query::Symbol("pi".to_string())),
+                ContextSelector::Domain("math".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_union_and_group() {
+        let q = parse_query("(apple | pomme) @global").unwrap();
+        match q {
+            Query::InContext(inner, ContextSelector::Global) => {
+                assert!(matches!(*inner, Query::Group(_)));
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bad_selector() {
+        assert!(matches!(
+            parse_query("pi @bogus"),
+            Err(QueryError::BadSelector(_))
+        ));
+    }
+
+    #[test]
+    fn test_trailing_token_rejected() {
+        assert!(parse_query("a b").is_err());
+    }
+}
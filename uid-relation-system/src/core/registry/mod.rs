@@ -0,0 +1,142 @@
+//! 运行时自省注册表
+//!
+//! 借鉴 procfs 枚举内核活跃对象的思路，把生成器状态与全部已注册的符号/系统UID
+//! 导出为一个结构化、可 serde 序列化的快照，便于工具转储整个活跃的UID/符号命名空间
+//! 以用于调试与跨节点对账。
+
+use serde::{Deserialize, Serialize};
+use crate::core::symbol_map::{global_symbol_table, Context};
+use crate::core::types::UID;
+use crate::core::uid_gen::global_generator;
+use crate::core::system_uids::{self, uid_ranges};
+
+/// 单个符号条目及其各上下文下的UID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolEntry {
+    pub symbol: String,
+    pub mappings: Vec<(Context, UID)>,
+}
+
+/// 生成器运行指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorMetrics {
+    pub machine_id: u16,
+    pub process_id: u8,
+    /// 最近发放UID所用的时间戳（高水位）
+    pub last_timestamp: u64,
+    /// 当前毫秒内的序列号水位
+    pub sequence: u64,
+    /// 仍在占用的临时UID数量
+    pub temp_uids_outstanding: usize,
+}
+
+/// 注册表快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    /// 全部已注册符号及其映射
+    pub symbols: Vec<SymbolEntry>,
+    /// 预定义系统UID，按符号名键出（REL_*、PATTERN_*、ERROR_*、OP_*、标记）
+    pub system_uids: Vec<(String, UID)>,
+    /// 生成器指标
+    pub generator: GeneratorMetrics,
+}
+
+/// 全部预定义系统符号名
+const SYSTEM_SYMBOLS: &[&str] = &[
+    system_uids::REL_ROLE_SYMBOL,
+    system_uids::REL_CONTEXT_SYMBOL,
+    system_uids::REL_MEANING_SYMBOL,
+    system_uids::PATTERN_WILDCARD_SYMBOL,
+    system_uids::PATTERN_WILDCARD_MULTI_SYMBOL,
+    system_uids::PATTERN_SET_START_SYMBOL,
+    system_uids::PATTERN_SET_END_SYMBOL,
+    system_uids::PATTERN_NOT_SYMBOL,
+    system_uids::PATTERN_MIN_MAX_SYMBOL,
+    system_uids::ERROR_START_SYMBOL,
+    system_uids::ERROR_FATAL_SYMBOL,
+    system_uids::ERROR_SEVERE_SYMBOL,
+    system_uids::ERROR_WARNING_SYMBOL,
+    system_uids::ERROR_INFO_SYMBOL,
+    system_uids::OP_MOVE_SYMBOL,
+    system_uids::OP_INSERT_SYMBOL,
+    system_uids::OP_DELETE_SYMBOL,
+    system_uids::OP_COPY_SYMBOL,
+    system_uids::OP_RELATE_SYMBOL,
+    system_uids::OP_SEARCH_SYMBOL,
+    system_uids::OP_EXECUTE_SYMBOL,
+    system_uids::ANCHOR_MARKER_SYMBOL,
+    system_uids::START_MARKER_SYMBOL,
+    system_uids::EXEC_MARKER_SYMBOL,
+    system_uids::SUCCESS_MARKER_SYMBOL,
+    system_uids::FAILURE_MARKER_SYMBOL,
+];
+
+/// 采集整个系统的运行时快照
+pub fn snapshot() -> RegistrySnapshot {
+    // 仅持有读锁期间导出符号条目，避免与其他锁嵌套
+    let entries = {
+        let table = global_symbol_table().read().unwrap();
+        table.snapshot_entries()
+    };
+
+    // 从条目的全局上下文映射中挑出系统符号的UID
+    let global_uid = |name: &str| -> Option<UID> {
+        let key = name.to_lowercase();
+        entries
+            .iter()
+            .find(|(sym, _)| sym == &key)
+            .and_then(|(_, mappings)| {
+                mappings
+                    .iter()
+                    .find(|(ctx, _)| matches!(ctx, Context::Global))
+                    .map(|(_, uid)| *uid)
+            })
+    };
+
+    let system_uids = SYSTEM_SYMBOLS
+        .iter()
+        .filter_map(|name| global_uid(name).map(|uid| (name.to_string(), uid)))
+        .collect();
+
+    let symbols = entries
+        .into_iter()
+        .map(|(symbol, mappings)| SymbolEntry { symbol, mappings })
+        .collect();
+
+    let generator = global_generator();
+    let metrics = GeneratorMetrics {
+        machine_id: generator.machine_id(),
+        process_id: generator.process_id(),
+        last_timestamp: generator.last_timestamp(),
+        sequence: generator.sequence(),
+        temp_uids_outstanding: uid_ranges::temp_uids_outstanding(),
+    };
+
+    RegistrySnapshot {
+        symbols,
+        system_uids,
+        generator: metrics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_contains_system_uids() {
+        system_uids::initialize_system_uids().unwrap();
+
+        let snap = snapshot();
+        // 系统UID应被枚举出来
+        assert!(snap
+            .system_uids
+            .iter()
+            .any(|(name, _)| name == system_uids::OP_MOVE_SYMBOL));
+        assert!(!snap.symbols.is_empty());
+
+        // 快照应可序列化
+        let json = serde_json::to_string(&snap).unwrap();
+        assert!(json.contains("generator"));
+    }
+}
@@ -5,6 +5,9 @@
 //! 反向映射：UID -> 基础符号（可能有多个符号指向同一个UID）
 
 use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 use crate::core::types::UID;
 use crate::core::uid_gen;
@@ -12,9 +15,10 @@ use serde::{Deserialize, Serialize};
 use lazy_static::lazy_static;
 
 /// 上下文标识符
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Context {
     /// 全局上下文（默认）
+    #[default]
     Global,
     /// 特定领域上下文
     Domain(String),
@@ -24,9 +28,36 @@ pub enum Context {
     Temporary,
 }
 
-impl Default for Context {
-    fn default() -> Self {
-        Context::Global
+/// 以 `Context` 为键的映射的序列化助手
+///
+/// `Context` 是带数据变体（`Domain(String)`/`Custom(UID)`）的外部标签枚举，
+/// 不是字符串，serde_json 会拒绝把它当作 JSON 对象的键。这里统一把这类映射
+/// 持久化为 `[(Context, V), ...]` 序列，绕开“键必须是字符串”的限制。
+mod context_map_serde {
+    use super::{Context, HashMap};
+    use serde::de::Deserialize;
+    use serde::ser::SerializeSeq;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, V>(map: &HashMap<Context, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        let mut seq = serializer.serialize_seq(Some(map.len()))?;
+        for entry in map {
+            seq.serialize_element(&entry)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D, V>(deserializer: D) -> Result<HashMap<Context, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let entries: Vec<(Context, V)> = Vec::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
     }
 }
 
@@ -36,11 +67,15 @@ pub struct SymbolMapping {
     /// 基础符号字符串
     pub symbol: String,
     /// 符号的UID映射（按上下文）
+    #[serde(with = "context_map_serde")]
     pub mappings: HashMap<Context, UID>,
     /// 创建时间
     pub created_at: u64,
     /// 最后访问时间
     pub last_accessed: u64,
+    /// 访问次数（用于频率感知的淘汰）
+    #[serde(default)]
+    pub access_count: u32,
 }
 
 impl SymbolMapping {
@@ -54,17 +89,28 @@ impl SymbolMapping {
             mappings: HashMap::new(),
             created_at: now,
             last_accessed: now,
+            access_count: 0,
         }
     }
-    
+
     /// 在指定上下文中获取UID
     pub fn get_uid(&mut self, context: &Context) -> Option<UID> {
         self.last_accessed = crate::core::uid_gen::global_generator()
             .current_timestamp()
             .unwrap_or(0);
-        
+        self.access_count = self.access_count.saturating_add(1);
+
         self.mappings.get(context).copied()
     }
+
+    /// 按访问频率和时间衰减计算缓存分值
+    ///
+    /// `score = access_count * 0.5^(age_ms / half_life_ms)`，分值越低越该淘汰。
+    fn eviction_score(&self, now: u64, half_life_ms: f64) -> f64 {
+        let age = now.saturating_sub(self.last_accessed) as f64;
+        let half_life = if half_life_ms <= 0.0 { 1.0 } else { half_life_ms };
+        self.access_count as f64 * 0.5f64.powf(age / half_life)
+    }
     
     /// 在指定上下文中设置UID
     pub fn set_uid(&mut self, context: Context, uid: UID) {
@@ -93,8 +139,68 @@ pub struct SymbolTable {
     symbol_to_mapping: HashMap<String, SymbolMapping>,
     /// UID -> 符号列表（反向映射，支持多对一）
     uid_to_symbols: HashMap<UID, HashSet<String>>,
+    /// 每个上下文可配置的回退链（在默认链之外覆盖）
+    #[serde(with = "context_map_serde", default)]
+    fallback_chains: HashMap<Context, Vec<Context>>,
     /// 符号统计
     stats: SymbolStats,
+    /// 持久化句柄（不随状态序列化）
+    #[serde(skip)]
+    persistence: Option<Persistence>,
+}
+
+/// WAL操作码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalOp {
+    /// 注册新符号并分配UID
+    Register,
+    /// 设置（覆盖）符号-UID映射
+    SetMapping,
+    /// 移除某上下文的映射
+    RemoveMapping,
+}
+
+/// 单条预写日志记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalRecord {
+    pub op: WalOp,
+    pub symbol: String,
+    pub context: Context,
+    /// 移除操作没有UID
+    pub uid: Option<UID>,
+    pub timestamp: u64,
+}
+
+/// fsync策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// 每条记录后都fsync（最安全，最慢）
+    Always,
+    /// 从不显式fsync，交给操作系统刷盘
+    Never,
+    /// 每N条记录fsync一次
+    Every(usize),
+}
+
+/// 持久化运行时状态
+///
+/// 每次变更调用都会向WAL追加一条记录；配置了 `snapshot_path` 时，达到轮转阈值
+/// 后自动做一次快照并截断日志，未配置时不轮转。该结构不参与 `SymbolTable` 的序列化。
+#[derive(Debug)]
+struct Persistence {
+    wal_path: PathBuf,
+    snapshot_path: Option<PathBuf>,
+    wal: File,
+    records_since_snapshot: usize,
+    rotation_threshold: usize,
+    fsync_policy: FsyncPolicy,
+    writes_since_fsync: usize,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolTable {
@@ -102,16 +208,257 @@ impl SymbolTable {
         Self {
             symbol_to_mapping: HashMap::new(),
             uid_to_symbols: HashMap::new(),
+            fallback_chains: HashMap::new(),
             stats: SymbolStats::new(),
+            persistence: None,
         }
     }
+
+    /// 启用WAL持久化
+    ///
+    /// WAL以追加模式打开，`rotation_threshold` 条记录后自动触发快照+截断
+    /// （仅当 `snapshot_path` 为 `Some` 且阈值大于0），`fsync_policy` 控制刷盘节奏。
+    pub fn enable_persistence(
+        &mut self,
+        wal_path: impl AsRef<Path>,
+        snapshot_path: Option<PathBuf>,
+        rotation_threshold: usize,
+        fsync_policy: FsyncPolicy,
+    ) -> Result<(), SymbolError> {
+        let wal_path = wal_path.as_ref().to_path_buf();
+        let wal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&wal_path)
+            .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+
+        self.persistence = Some(Persistence {
+            wal_path,
+            snapshot_path,
+            wal,
+            records_since_snapshot: 0,
+            rotation_threshold,
+            fsync_policy,
+            writes_since_fsync: 0,
+        });
+        Ok(())
+    }
+
+    /// 向WAL追加一条记录，并在需要时触发轮转
+    fn log_wal(
+        &mut self,
+        op: WalOp,
+        symbol: &str,
+        context: &Context,
+        uid: Option<UID>,
+    ) -> Result<(), SymbolError> {
+        if self.persistence.is_none() {
+            return Ok(());
+        }
+
+        let timestamp = crate::core::uid_gen::global_generator()
+            .current_timestamp()
+            .unwrap_or(0);
+        let record = WalRecord {
+            op,
+            symbol: symbol.to_lowercase(),
+            context: context.clone(),
+            uid,
+            timestamp,
+        };
+
+        let line = serde_json::to_string(&record)
+            .map_err(|e| SymbolError::SerializationError(e.to_string()))?;
+
+        let rotate = {
+            let p = self.persistence.as_mut().unwrap();
+            writeln!(p.wal, "{}", line)
+                .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+
+            p.records_since_snapshot += 1;
+            p.writes_since_fsync += 1;
+
+            let should_fsync = match p.fsync_policy {
+                FsyncPolicy::Always => true,
+                FsyncPolicy::Never => false,
+                FsyncPolicy::Every(n) => n > 0 && p.writes_since_fsync >= n,
+            };
+            if should_fsync {
+                p.wal
+                    .sync_data()
+                    .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+                p.writes_since_fsync = 0;
+            }
+
+            p.rotation_threshold > 0 && p.records_since_snapshot >= p.rotation_threshold
+        };
+
+        if rotate {
+            if let Some(path) = self
+                .persistence
+                .as_ref()
+                .and_then(|p| p.snapshot_path.clone())
+            {
+                self.snapshot(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 写入完整状态快照并截断WAL
+    ///
+    /// 先写临时文件再原子重命名，随后清空日志，使快照成为新的恢复基线。
+    pub fn snapshot(&mut self, path: impl AsRef<Path>) -> Result<(), SymbolError> {
+        let path = path.as_ref().to_path_buf();
+        let data = serde_json::to_vec(self)
+            .map_err(|e| SymbolError::SerializationError(e.to_string()))?;
+
+        let tmp = path.with_extension("tmp");
+        {
+            let mut f = File::create(&tmp)
+                .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+            f.write_all(&data)
+                .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+            f.sync_data()
+                .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+        }
+        std::fs::rename(&tmp, &path)
+            .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+
+        // 快照落盘后截断WAL并重新打开追加句柄
+        if let Some(p) = self.persistence.as_mut() {
+            let wal = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&p.wal_path)
+                .map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+            p.wal = wal;
+            p.records_since_snapshot = 0;
+            p.writes_since_fsync = 0;
+        }
+
+        Ok(())
+    }
+
+    /// 从快照加载状态，再按顺序重放WAL记录
+    ///
+    /// 快照不存在时以空表开始；WAL不存在时视为无待重放记录。
+    pub fn recover(
+        snapshot_path: impl AsRef<Path>,
+        wal_path: impl AsRef<Path>,
+    ) -> Result<Self, SymbolError> {
+        let mut table = match File::open(snapshot_path.as_ref()) {
+            Ok(f) => serde_json::from_reader(BufReader::new(f))
+                .map_err(|e| SymbolError::DeserializationError(e.to_string()))?,
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => SymbolTable::new(),
+            Err(e) => return Err(SymbolError::PersistenceError(e.to_string())),
+        };
+
+        match File::open(wal_path.as_ref()) {
+            Ok(f) => {
+                for line in BufReader::new(f).lines() {
+                    let line = line.map_err(|e| SymbolError::PersistenceError(e.to_string()))?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let record: WalRecord = serde_json::from_str(&line)
+                        .map_err(|e| SymbolError::DeserializationError(e.to_string()))?;
+                    table.apply_record(&record);
+                }
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(SymbolError::PersistenceError(e.to_string())),
+        }
+
+        // 重放经 `set_mapping`/`remove_mapping` 进行，二者已增量维护计数；此处仍从
+        // 最终状态重算一次作为权威对账，使计数与恢复出的条目严格一致。
+        table.stats.total_symbols = table.symbol_to_mapping.len();
+        table.stats.unique_uids = table.uid_to_symbols.len();
+
+        Ok(table)
+    }
+
+    /// 重放单条WAL记录（不再回写日志）
+    fn apply_record(&mut self, record: &WalRecord) {
+        match record.op {
+            WalOp::Register | WalOp::SetMapping => {
+                if let Some(uid) = record.uid {
+                    let _ = self.set_mapping(&record.symbol, record.context.clone(), uid);
+                }
+            }
+            WalOp::RemoveMapping => {
+                self.remove_mapping(&record.symbol, &record.context);
+            }
+        }
+    }
+
+    /// 为某个上下文注册自定义回退链
+    ///
+    /// 注册后，`get_uid_resolved` 会在请求的上下文之后依次尝试链中的上下文，
+    /// 取代该上下文的默认链。
+    pub fn set_fallback_chain(&mut self, context: Context, chain: Vec<Context>) {
+        self.fallback_chains.insert(context, chain);
+    }
+
+    /// 计算某个上下文的解析顺序（请求的上下文在前，最后回退到全局）
+    ///
+    /// 点号命名的领域按层级逐级回退：`Domain("math.algebra")` →
+    /// `Domain("math")` → `Global`。若注册过自定义回退链，则使用之。
+    fn resolution_chain(&self, context: &Context) -> Vec<Context> {
+        if let Some(custom) = self.fallback_chains.get(context) {
+            let mut chain = vec![context.clone()];
+            chain.extend(custom.iter().cloned());
+            return chain;
+        }
+
+        match context {
+            Context::Global => vec![Context::Global],
+            Context::Domain(name) => {
+                let parts: Vec<&str> = name.split('.').collect();
+                let mut chain = Vec::with_capacity(parts.len() + 1);
+                for i in (1..=parts.len()).rev() {
+                    chain.push(Context::Domain(parts[..i].join(".")));
+                }
+                chain.push(Context::Global);
+                chain
+            }
+            other => vec![other.clone(), Context::Global],
+        }
+    }
+
+    /// 按解析顺序查找符号的UID，返回首个命中及满足它的上下文
+    ///
+    /// 这实现了“一词多义”目标中的继承语义：领域查找在没有自身映射时
+    /// 会继承更宽泛（直至全局）的含义。
+    pub fn get_uid_resolved(&mut self, symbol: &str, context: &Context) -> Option<(UID, Context)> {
+        self.stats.lookups += 1;
+
+        let symbol_lower = symbol.to_lowercase();
+        let chain = self.resolution_chain(context);
+
+        let mapping = self.symbol_to_mapping.get_mut(&symbol_lower)?;
+        for ctx in &chain {
+            if let Some(uid) = mapping.get_uid(ctx) {
+                self.stats.cache_hits += 1;
+                return Some((uid, ctx.clone()));
+            }
+        }
+
+        None
+    }
     
     /// 注册新符号（如果不存在）
     pub fn register_symbol(&mut self, symbol: &str, context: Context) -> Result<UID, SymbolError> {
-        self.stats.total_symbols += 1;
-        
         let symbol_lower = symbol.to_lowercase();
-        
+
+        // 仅在首次见到该符号时计数，使 `total_symbols` 与 `symbol_to_mapping.len()`
+        // （`recover` 重算出的值）保持一致——同一符号的多个上下文不应重复计入
+        if !self.symbol_to_mapping.contains_key(&symbol_lower) {
+            self.stats.total_symbols += 1;
+        }
+
         let mapping = self.symbol_to_mapping
             .entry(symbol_lower.clone())
             .or_insert_with(|| SymbolMapping::new(symbol.to_string()));
@@ -132,14 +479,16 @@ impl SymbolTable {
         // 更新反向映射
         self.uid_to_symbols
             .entry(uid)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(symbol_lower);
-        
-        self.stats.unique_uids += 1;
-        
+
+        self.stats.unique_uids = self.uid_to_symbols.len();
+
+        self.log_wal(WalOp::Register, symbol, &context, Some(uid))?;
+
         Ok(uid)
     }
-    
+
     /// 获取符号在指定上下文中的UID
     pub fn get_uid(&mut self, symbol: &str, context: &Context) -> Option<UID> {
         self.stats.lookups += 1;
@@ -156,6 +505,127 @@ impl SymbolTable {
         None
     }
     
+    /// 使用glob通配模式查找匹配的符号键，返回其在指定上下文中的UID
+    ///
+    /// 支持 `*`（匹配零个或多个字符）、`?`（匹配恰好一个字符）
+    /// 以及 `[abc]` / `[^abc]` 字符集。与 `get_uid` 一致，按小写比较。
+    pub fn find_matching(&self, pattern: &str, context: &Context) -> Vec<(String, UID)> {
+        let tokens = parse_glob(&pattern.to_lowercase());
+        let mut result = Vec::new();
+        for (symbol, mapping) in &self.symbol_to_mapping {
+            if glob_match(&tokens, symbol) {
+                if let Some(uid) = mapping.mappings.get(context).copied() {
+                    result.push((symbol.clone(), uid));
+                }
+            }
+        }
+        result
+    }
+
+    /// 在所有上下文中查找匹配glob模式的符号键
+    ///
+    /// 返回匹配符号命中的每一个上下文及其对应UID。
+    pub fn find_matching_all_contexts(&self, pattern: &str) -> Vec<(String, Context, UID)> {
+        let tokens = parse_glob(&pattern.to_lowercase());
+        let mut result = Vec::new();
+        for (symbol, mapping) in &self.symbol_to_mapping {
+            if glob_match(&tokens, symbol) {
+                for (ctx, uid) in &mapping.mappings {
+                    result.push((symbol.clone(), ctx.clone(), *uid));
+                }
+            }
+        }
+        result
+    }
+
+    /// 执行查询DSL并返回 (符号, 上下文, UID) 三元组集合
+    ///
+    /// 见 [`crate::core::query`]：`@domain:*` 枚举符号命中的所有领域上下文，
+    /// `|` 对结果集取并集（去重）。
+    pub fn run_query(
+        &self,
+        query: &crate::core::query::Query,
+    ) -> Vec<(String, Context, UID)> {
+        use crate::core::query::Query;
+
+        match query {
+            Query::Symbol(s) => self
+                .find_matching(s, &Context::Global)
+                .into_iter()
+                .map(|(sym, uid)| (sym, Context::Global, uid))
+                .collect(),
+            Query::Group(inner) => self.run_query(inner),
+            Query::Or(a, b) => dedup_tuples(self.run_query(a).into_iter().chain(self.run_query(b))),
+            Query::InContext(inner, selector) => self.run_query_scoped(inner, selector),
+        }
+    }
+
+    /// 在给定上下文选择器下求值子查询
+    ///
+    /// 把选择器下推到每个裸符号叶子，使 `(apple | pomme) @domain:math` 这类
+    /// 分组 / 并集查询与展开后的 `apple @domain:math | pomme @domain:math` 等价，
+    /// 而不是先在全局求值再被领域过滤器全部丢弃。
+    fn run_query_scoped(
+        &self,
+        query: &crate::core::query::Query,
+        selector: &crate::core::query::ContextSelector,
+    ) -> Vec<(String, Context, UID)> {
+        use crate::core::query::Query;
+
+        match query {
+            Query::Symbol(s) => self.resolve_in_selector(s, selector),
+            Query::Group(inner) => self.run_query_scoped(inner, selector),
+            Query::Or(a, b) => dedup_tuples(
+                self.run_query_scoped(a, selector)
+                    .into_iter()
+                    .chain(self.run_query_scoped(b, selector)),
+            ),
+            // 内层显式上下文覆盖外层选择器
+            Query::InContext(inner, inner_selector) => self.run_query_scoped(inner, inner_selector),
+        }
+    }
+
+    /// 在选择器指定的上下文中解析单个符号（裸符号的直接定向查找）
+    fn resolve_in_selector(
+        &self,
+        symbol: &str,
+        selector: &crate::core::query::ContextSelector,
+    ) -> Vec<(String, Context, UID)> {
+        use crate::core::query::{selector_matches, ContextSelector};
+
+        match selector {
+            ContextSelector::Global => self
+                .find_matching(symbol, &Context::Global)
+                .into_iter()
+                .map(|(sym, uid)| (sym, Context::Global, uid))
+                .collect(),
+            ContextSelector::Temp => self
+                .find_matching(symbol, &Context::Temporary)
+                .into_iter()
+                .map(|(sym, uid)| (sym, Context::Temporary, uid))
+                .collect(),
+            ContextSelector::Domain(name) => {
+                let ctx = Context::Domain(name.clone());
+                self.find_matching(symbol, &ctx)
+                    .into_iter()
+                    .map(|(sym, uid)| (sym, ctx.clone(), uid))
+                    .collect()
+            }
+            ContextSelector::Custom(uid) => {
+                let ctx = Context::Custom(*uid);
+                self.find_matching(symbol, &ctx)
+                    .into_iter()
+                    .map(|(sym, uid)| (sym, ctx.clone(), uid))
+                    .collect()
+            }
+            ContextSelector::DomainAny => self
+                .find_matching_all_contexts(symbol)
+                .into_iter()
+                .filter(|(_, ctx, _)| selector_matches(selector, ctx))
+                .collect(),
+        }
+    }
+
     /// 获取所有指向指定UID的符号
     pub fn get_symbols_for_uid(&self, uid: UID) -> Vec<String> {
         self.uid_to_symbols
@@ -165,11 +635,11 @@ impl SymbolTable {
     }
     
     /// 获取UID的基本符号（在全局上下文中）
-    pub fn get_base_symbol(&mut self, uid: UID) -> Option<String> {
+    pub fn get_base_symbol(&self, uid: UID) -> Option<String> {
         // 首先检查全局上下文
         for (symbol, mapping) in &self.symbol_to_mapping {
-            if let Some(mapped_uid) = mapping.get_uid(&Context::Global) {
-                if mapped_uid == uid {
+            if let Some(mapped_uid) = mapping.mappings.get(&Context::Global) {
+                if *mapped_uid == uid {
                     return Some(symbol.clone());
                 }
             }
@@ -185,7 +655,11 @@ impl SymbolTable {
     /// 在指定上下文中设置符号-UID映射
     pub fn set_mapping(&mut self, symbol: &str, context: Context, uid: UID) -> Result<(), SymbolError> {
         let symbol_lower = symbol.to_lowercase();
-        
+
+        // 首次见到该符号时登记，使 `total_symbols` 与 `remove_mapping` 的递减配平
+        // （WAL重放只走 set_mapping，不走 register_symbol）
+        let is_new_symbol = !self.symbol_to_mapping.contains_key(&symbol_lower);
+
         let mapping = self.symbol_to_mapping
             .entry(symbol_lower.clone())
             .or_insert_with(|| SymbolMapping::new(symbol.to_string()));
@@ -205,25 +679,34 @@ impl SymbolTable {
         }
         
         // 设置新映射
+        let logged_ctx = context.clone();
         mapping.set_uid(context, uid);
-        
+
         // 更新反向映射
         self.uid_to_symbols
             .entry(uid)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(symbol_lower);
-        
+
         self.stats.unique_uids = self.uid_to_symbols.len();
-        
+        if is_new_symbol {
+            self.stats.total_symbols += 1;
+        }
+
+        self.log_wal(WalOp::SetMapping, symbol, &logged_ctx, Some(uid))?;
+
         Ok(())
     }
     
     /// 移除指定上下文中的映射
     pub fn remove_mapping(&mut self, symbol: &str, context: &Context) -> bool {
         let symbol_lower = symbol.to_lowercase();
-        
+        let mut removed = false;
+
         if let Some(mapping) = self.symbol_to_mapping.get_mut(&symbol_lower) {
             if let Some(uid) = mapping.mappings.remove(context) {
+                removed = true;
+
                 // 更新反向映射
                 if let Some(symbols) = self.uid_to_symbols.get_mut(&uid) {
                     symbols.remove(&symbol_lower);
@@ -232,20 +715,40 @@ impl SymbolTable {
                         self.stats.unique_uids -= 1;
                     }
                 }
-                
+
                 // 如果映射为空，删除整个条目
                 if mapping.mappings.is_empty() {
                     self.symbol_to_mapping.remove(&symbol_lower);
                     self.stats.total_symbols -= 1;
                 }
-                
-                return true;
             }
         }
-        
-        false
+
+        if removed {
+            // 变更已提交，尽力记录到WAL（失败不影响内存状态）
+            let _ = self.log_wal(WalOp::RemoveMapping, &symbol_lower, context, None);
+        }
+
+        removed
     }
     
+    /// 导出所有符号条目及其各上下文映射的只读快照
+    ///
+    /// 供 [`crate::core::registry`] 枚举活跃的UID/符号命名空间。
+    pub fn snapshot_entries(&self) -> Vec<(String, Vec<(Context, UID)>)> {
+        self.symbol_to_mapping
+            .iter()
+            .map(|(symbol, mapping)| {
+                let mappings = mapping
+                    .mappings
+                    .iter()
+                    .map(|(ctx, uid)| (ctx.clone(), *uid))
+                    .collect();
+                (symbol.clone(), mappings)
+            })
+            .collect()
+    }
+
     /// 获取所有符号
     pub fn get_all_symbols(&self) -> Vec<String> {
         self.symbol_to_mapping.keys().cloned().collect()
@@ -256,47 +759,204 @@ impl SymbolTable {
         &self.stats
     }
     
-    /// 清理不常用的符号（LRU策略）
+    /// 基于“年龄 + 访问频率”双因子的清理
+    ///
+    /// 仅当符号 `age > max_age_ms` 且 `access_count < min_access_count` 时才淘汰，
+    /// 即老旧且冷门的条目。返回被淘汰的符号数量。
     pub fn cleanup(&mut self, max_age_ms: u64, min_access_count: u32) -> usize {
         let now = crate::core::uid_gen::global_generator()
             .current_timestamp()
             .unwrap_or(0);
-        
-        let mut to_remove = Vec::new();
-        
-        for (symbol, mapping) in &self.symbol_to_mapping {
-            // 计算未访问时间
-            let age = now.saturating_sub(mapping.last_accessed);
-            
-            // 简单的LRU策略
-            if age > max_age_ms {
-                // 可以添加更复杂的访问计数检查
-                to_remove.push(symbol.clone());
-            }
-        }
-        
+
+        let to_remove: Vec<String> = self
+            .symbol_to_mapping
+            .iter()
+            .filter(|(_, mapping)| {
+                let age = now.saturating_sub(mapping.last_accessed);
+                age > max_age_ms && mapping.access_count < min_access_count
+            })
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+
         let removed_count = to_remove.len();
-        
         for symbol in to_remove {
-            // 移除符号的所有映射
-            if let Some(mapping) = self.symbol_to_mapping.remove(&symbol) {
-                for (_, uid) in mapping.mappings {
-                    if let Some(symbols) = self.uid_to_symbols.get_mut(&uid) {
-                        symbols.remove(&symbol);
-                        if symbols.is_empty() {
-                            self.uid_to_symbols.remove(&uid);
-                        }
-                    }
-                }
-            }
+            self.evict_symbol(&symbol);
         }
-        
+
         self.stats.total_symbols = self.symbol_to_mapping.len();
         self.stats.unique_uids = self.uid_to_symbols.len();
-        self.stats.cleanup_count += removed_count;
-        
+        self.stats.cleanup_count += removed_count as u64;
+        self.stats.evictions += removed_count as u64;
+
+        removed_count
+    }
+
+    /// 将符号总数收敛到容量上限，淘汰分值最低的条目
+    ///
+    /// 分值由 [`SymbolMapping::eviction_score`] 计算（访问次数按时间衰减）。
+    /// 返回被淘汰的符号数量。
+    pub fn evict_to(&mut self, max_symbols: usize, half_life_ms: f64) -> usize {
+        if self.symbol_to_mapping.len() <= max_symbols {
+            return 0;
+        }
+
+        let now = crate::core::uid_gen::global_generator()
+            .current_timestamp()
+            .unwrap_or(0);
+
+        // 按分值升序排序，分值最低者优先淘汰
+        let mut scored: Vec<(String, f64)> = self
+            .symbol_to_mapping
+            .iter()
+            .map(|(symbol, mapping)| (symbol.clone(), mapping.eviction_score(now, half_life_ms)))
+            .collect();
+        scored.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let to_drop = self.symbol_to_mapping.len() - max_symbols;
+        let victims: Vec<String> = scored.into_iter().take(to_drop).map(|(s, _)| s).collect();
+
+        let removed_count = victims.len();
+        for symbol in victims {
+            self.evict_symbol(&symbol);
+        }
+
+        self.stats.total_symbols = self.symbol_to_mapping.len();
+        self.stats.unique_uids = self.uid_to_symbols.len();
+        self.stats.evictions += removed_count as u64;
+
         removed_count
     }
+
+    /// 移除单个符号的所有映射并同步反向索引
+    fn evict_symbol(&mut self, symbol: &str) {
+        if let Some(mapping) = self.symbol_to_mapping.remove(symbol) {
+            for uid in mapping.mappings.values() {
+                if let Some(symbols) = self.uid_to_symbols.get_mut(uid) {
+                    symbols.remove(symbol);
+                    if symbols.is_empty() {
+                        self.uid_to_symbols.remove(uid);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// glob模式的单个语法元素
+enum GlobToken {
+    /// 字面字符
+    Literal(char),
+    /// `?`：匹配恰好一个字符
+    AnyOne,
+    /// `*`：匹配零个或多个字符
+    AnyMany,
+    /// `[abc]` / `[^abc]`：字符集
+    Set { negated: bool, chars: Vec<char> },
+}
+
+/// 将glob模式字符串解析为语法元素序列
+///
+/// 没有闭合 `]` 的 `[` 退化为字面量。
+fn parse_glob(pattern: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                tokens.push(GlobToken::AnyMany);
+                i += 1;
+            }
+            '?' => {
+                tokens.push(GlobToken::AnyOne);
+                i += 1;
+            }
+            '[' => {
+                let mut j = i + 1;
+                let negated = j < chars.len() && chars[j] == '^';
+                if negated {
+                    j += 1;
+                }
+                let mut set = Vec::new();
+                while j < chars.len() && chars[j] != ']' {
+                    set.push(chars[j]);
+                    j += 1;
+                }
+                if j < chars.len() {
+                    tokens.push(GlobToken::Set { negated, chars: set });
+                    i = j + 1;
+                } else {
+                    // 缺少闭合括号，`[` 当作字面量处理
+                    tokens.push(GlobToken::Literal('['));
+                    i += 1;
+                }
+            }
+            c => {
+                tokens.push(GlobToken::Literal(c));
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+/// 判断单个语法元素是否匹配字符 `c`
+fn token_matches(token: &GlobToken, c: char) -> bool {
+    match token {
+        GlobToken::Literal(l) => *l == c,
+        GlobToken::AnyOne => true,
+        GlobToken::AnyMany => false,
+        GlobToken::Set { negated, chars } => chars.contains(&c) != *negated,
+    }
+}
+
+/// 经典线性双指针回溯的glob匹配
+///
+/// 同时推进模式指针 `p` 和文本指针 `t`；遇到 `*` 记录 `star=p; mark=t`
+/// 并只推进 `p`；失配时若见过 `*` 则令 `p=star+1; mark+=1; t=mark`
+/// （让星号多吞一个字符），否则匹配失败；末尾跳过尾随 `*` 并要求两者都耗尽。
+fn glob_match(tokens: &[GlobToken], text: &str) -> bool {
+    let t: Vec<char> = text.chars().collect();
+    let mut p = 0usize;
+    let mut ti = 0usize;
+    let mut star: Option<usize> = None;
+    let mut mark = 0usize;
+
+    while ti < t.len() {
+        if p < tokens.len() && matches!(tokens[p], GlobToken::AnyMany) {
+            star = Some(p);
+            mark = ti;
+            p += 1;
+        } else if p < tokens.len() && token_matches(&tokens[p], t[ti]) {
+            p += 1;
+            ti += 1;
+        } else if let Some(s) = star {
+            p = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+
+    while p < tokens.len() && matches!(tokens[p], GlobToken::AnyMany) {
+        p += 1;
+    }
+    p == tokens.len()
+}
+
+/// 对查询结果三元组取并集并按首次出现顺序去重
+fn dedup_tuples(
+    items: impl Iterator<Item = (String, Context, UID)>,
+) -> Vec<(String, Context, UID)> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for item in items {
+        if seen.insert(item.clone()) {
+            out.push(item);
+        }
+    }
+    out
 }
 
 /// 符号统计信息
@@ -312,6 +972,15 @@ pub struct SymbolStats {
     pub cache_hits: u64,
     /// 清理次数
     pub cleanup_count: u64,
+    /// 淘汰条目总数（cleanup 与 evict_to 合计）
+    #[serde(default)]
+    pub evictions: u64,
+}
+
+impl Default for SymbolStats {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolStats {
@@ -322,6 +991,7 @@ impl SymbolStats {
             lookups: 0,
             cache_hits: 0,
             cleanup_count: 0,
+            evictions: 0,
         }
     }
     
@@ -349,6 +1019,9 @@ pub enum SymbolError {
     
     #[error("反序列化错误: {0}")]
     DeserializationError(String),
+
+    #[error("持久化错误: {0}")]
+    PersistenceError(String),
 }
 
 // 全局符号表实例
@@ -381,10 +1054,32 @@ pub fn get_uid_in_context(symbol: &str, context: &Context) -> Option<UID> {
 
 /// 获取UID的基础符号
 pub fn get_base_symbol_for_uid(uid: UID) -> Option<String> {
-    let mut table = GLOBAL_SYMBOL_TABLE.write().unwrap();
+    let table = GLOBAL_SYMBOL_TABLE.read().unwrap();
     table.get_base_symbol(uid)
 }
 
+/// 为全局符号表启用WAL持久化
+pub fn enable_global_persistence(
+    wal_path: impl AsRef<Path>,
+    snapshot_path: Option<PathBuf>,
+    rotation_threshold: usize,
+    fsync_policy: FsyncPolicy,
+) -> Result<(), SymbolError> {
+    let mut table = GLOBAL_SYMBOL_TABLE.write().unwrap();
+    table.enable_persistence(wal_path, snapshot_path, rotation_threshold, fsync_policy)
+}
+
+/// 从快照+WAL恢复全局符号表，替换当前内存状态
+pub fn recover_global_symbol_table(
+    snapshot_path: impl AsRef<Path>,
+    wal_path: impl AsRef<Path>,
+) -> Result<(), SymbolError> {
+    let recovered = SymbolTable::recover(snapshot_path, wal_path)?;
+    let mut table = GLOBAL_SYMBOL_TABLE.write().unwrap();
+    *table = recovered;
+    Ok(())
+}
+
 /// 预注册系统常用符号
 pub fn pre_register_system_symbols() -> Result<(), SymbolError> {
     let mut table = GLOBAL_SYMBOL_TABLE.write().unwrap();
@@ -530,6 +1225,328 @@ mod tests {
         assert_eq!(table.get_uid("HELLO", &Context::Global).unwrap(), uid1);
     }
     
+    #[test]
+    fn test_run_query() {
+        use crate::core::query::parse_query;
+
+        let mut table = SymbolTable::new();
+        let apple = table.register_symbol("apple", Context::Global).unwrap();
+        let pomme = table.register_symbol("pomme", Context::Global).unwrap();
+        table.register_symbol("pi", Context::Domain("math".to_string())).unwrap();
+        table.register_symbol("pi", Context::Domain("physics".to_string())).unwrap();
+
+        // 并集查询
+        let q = parse_query("apple | pomme").unwrap();
+        let mut uids: Vec<UID> = table.run_query(&q).into_iter().map(|(_, _, u)| u).collect();
+        uids.sort();
+        let mut expected = vec![apple, pomme];
+        expected.sort();
+        assert_eq!(uids, expected);
+
+        // @domain:* 枚举所有领域上下文
+        let q = parse_query("pi @domain:*").unwrap();
+        let hits = table.run_query(&q);
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|(_, ctx, _)| matches!(ctx, Context::Domain(_))));
+    }
+
+    #[test]
+    fn test_run_query_grouped_or_in_context() {
+        use crate::core::query::parse_query;
+
+        let mut table = SymbolTable::new();
+        let fr_apple = table
+            .register_symbol("apple", Context::Domain("fr".to_string()))
+            .unwrap();
+        let fr_pomme = table
+            .register_symbol("pomme", Context::Domain("fr".to_string()))
+            .unwrap();
+
+        // 选择器必须下推到分组/并集的每个叶子，而非在全局求值后被过滤掉
+        let q = parse_query("(apple | pomme) @domain:fr").unwrap();
+        let hits = table.run_query(&q);
+        let mut uids: Vec<UID> = hits.iter().map(|(_, _, u)| *u).collect();
+        uids.sort();
+        let mut expected = vec![fr_apple, fr_pomme];
+        expected.sort();
+        assert_eq!(uids, expected);
+        assert!(hits
+            .iter()
+            .all(|(_, ctx, _)| *ctx == Context::Domain("fr".to_string())));
+    }
+
+    #[test]
+    fn test_resolved_falls_back_to_global() {
+        let mut table = SymbolTable::new();
+        let global_uid = table.register_symbol("pi", Context::Global).unwrap();
+
+        // math 领域没有自己的映射，应继承全局含义
+        let (uid, ctx) = table
+            .get_uid_resolved("pi", &Context::Domain("math".to_string()))
+            .unwrap();
+        assert_eq!(uid, global_uid);
+        assert_eq!(ctx, Context::Global);
+
+        // 领域有自身映射时优先返回自身
+        let math_uid = table
+            .register_symbol("pi", Context::Domain("math".to_string()))
+            .unwrap();
+        let (uid, ctx) = table
+            .get_uid_resolved("pi", &Context::Domain("math".to_string()))
+            .unwrap();
+        assert_eq!(uid, math_uid);
+        assert_eq!(ctx, Context::Domain("math".to_string()));
+    }
+
+    #[test]
+    fn test_resolved_nested_domain() {
+        let mut table = SymbolTable::new();
+        let math_uid = table
+            .register_symbol("root", Context::Domain("math".to_string()))
+            .unwrap();
+
+        // math.algebra 没有映射，应回退到 math
+        let (uid, ctx) = table
+            .get_uid_resolved("root", &Context::Domain("math.algebra".to_string()))
+            .unwrap();
+        assert_eq!(uid, math_uid);
+        assert_eq!(ctx, Context::Domain("math".to_string()));
+    }
+
+    #[test]
+    fn test_resolved_custom_chain() {
+        let mut table = SymbolTable::new();
+        let custom_uid = table
+            .register_symbol("x", Context::Domain("physics".to_string()))
+            .unwrap();
+
+        table.set_fallback_chain(
+            Context::Domain("chem".to_string()),
+            vec![Context::Domain("physics".to_string()), Context::Global],
+        );
+
+        let (uid, ctx) = table
+            .get_uid_resolved("x", &Context::Domain("chem".to_string()))
+            .unwrap();
+        assert_eq!(uid, custom_uid);
+        assert_eq!(ctx, Context::Domain("physics".to_string()));
+    }
+
+    #[test]
+    fn test_find_matching_glob() {
+        let mut table = SymbolTable::new();
+
+        table.register_symbol("op_move", Context::Global).unwrap();
+        table.register_symbol("op_copy", Context::Global).unwrap();
+        table.register_symbol("op_delete", Context::Global).unwrap();
+        table.register_symbol("pi", Context::Global).unwrap();
+
+        // `op_*` 应命中所有 op_ 前缀符号
+        let mut ops: Vec<String> = table
+            .find_matching("op_*", &Context::Global)
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect();
+        ops.sort();
+        assert_eq!(ops, vec!["op_copy", "op_delete", "op_move"]);
+
+        // `pi` 精确匹配
+        let exact = table.find_matching("pi", &Context::Global);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].0, "pi");
+
+        // `?` 匹配恰好一个字符
+        assert_eq!(table.find_matching("p?", &Context::Global).len(), 1);
+        assert!(table.find_matching("p???", &Context::Global).is_empty());
+    }
+
+    #[test]
+    fn test_find_matching_char_set() {
+        let mut table = SymbolTable::new();
+        table.register_symbol("cat", Context::Global).unwrap();
+        table.register_symbol("bat", Context::Global).unwrap();
+        table.register_symbol("rat", Context::Global).unwrap();
+
+        let mut hits: Vec<String> = table
+            .find_matching("[cb]at", &Context::Global)
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect();
+        hits.sort();
+        assert_eq!(hits, vec!["bat", "cat"]);
+
+        // 取反集合
+        let neg = table.find_matching("[^cb]at", &Context::Global);
+        assert_eq!(neg.len(), 1);
+        assert_eq!(neg[0].0, "rat");
+    }
+
+    #[test]
+    fn test_find_matching_all_contexts() {
+        let mut table = SymbolTable::new();
+        table.register_symbol("root", Context::Domain("math".to_string())).unwrap();
+        table.register_symbol("root", Context::Domain("botany".to_string())).unwrap();
+
+        let hits = table.find_matching_all_contexts("ro*");
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|(s, _, _)| s == "root"));
+    }
+
+    #[test]
+    fn test_wal_recover_roundtrip() {
+        let dir = std::env::temp_dir();
+        let tag = format!("urs_wal_{}", std::process::id());
+        let wal_path = dir.join(format!("{}.wal", tag));
+        let snap_path = dir.join(format!("{}.snap", tag));
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+
+        // 启用持久化并写入若干变更
+        let (apple_uid, pi_uid) = {
+            let mut table = SymbolTable::new();
+            table
+                .enable_persistence(&wal_path, Some(snap_path.clone()), 0, FsyncPolicy::Always)
+                .unwrap();
+            let apple = table.register_symbol("apple", Context::Global).unwrap();
+            let pi = table
+                .register_symbol("pi", Context::Domain("math".to_string()))
+                .unwrap();
+            // 移除应可被重放
+            table.remove_mapping("apple", &Context::Global);
+            (apple, pi)
+        };
+
+        // 从空快照+WAL恢复，移除后的 apple 不应复现
+        let recovered = SymbolTable::recover(&snap_path, &wal_path).unwrap();
+        assert!(!recovered.symbol_to_mapping.contains_key("apple"));
+        let mut r = recovered;
+        assert_eq!(
+            r.get_uid("pi", &Context::Domain("math".to_string())),
+            Some(pi_uid)
+        );
+        assert_ne!(apple_uid, pi_uid);
+
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+    }
+
+    #[test]
+    fn test_wal_recover_stats_after_remove() {
+        let dir = std::env::temp_dir();
+        let tag = format!("urs_recstats_{}", std::process::id());
+        let wal_path = dir.join(format!("{}.wal", tag));
+        let snap_path = dir.join(format!("{}.snap", tag));
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+
+        {
+            let mut table = SymbolTable::new();
+            table
+                .enable_persistence(&wal_path, Some(snap_path.clone()), 0, FsyncPolicy::Always)
+                .unwrap();
+            table.register_symbol("apple", Context::Global).unwrap();
+            table.register_symbol("banana", Context::Global).unwrap();
+            table.remove_mapping("apple", &Context::Global);
+        }
+
+        // 重放 Register+Register+Remove 不应使 total_symbols 下溢
+        let recovered = SymbolTable::recover(&snap_path, &wal_path).unwrap();
+        assert_eq!(recovered.get_stats().total_symbols, 1);
+        assert_eq!(recovered.get_stats().unique_uids, 1);
+        assert!(recovered.symbol_to_mapping.contains_key("banana"));
+        assert!(!recovered.symbol_to_mapping.contains_key("apple"));
+
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+    }
+
+    #[test]
+    fn test_register_symbol_total_symbols_counts_once() {
+        // 同一符号注册到多个上下文只应计入一次，live 值与 len() 一致
+        let mut table = SymbolTable::new();
+        table.register_symbol("pi", Context::Global).unwrap();
+        table
+            .register_symbol("pi", Context::Domain("math".to_string()))
+            .unwrap();
+        assert_eq!(table.get_stats().total_symbols, 1);
+        assert_eq!(table.symbol_to_mapping.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_rich_context() {
+        let dir = std::env::temp_dir();
+        let tag = format!("urs_richctx_{}", std::process::id());
+        let wal_path = dir.join(format!("{}.wal", tag));
+        let snap_path = dir.join(format!("{}.snap", tag));
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+
+        let (pi_uid, tau_uid) = {
+            let mut table = SymbolTable::new();
+            table
+                .enable_persistence(&wal_path, Some(snap_path.clone()), 0, FsyncPolicy::Always)
+                .unwrap();
+            let pi = table
+                .register_symbol("pi", Context::Domain("math".to_string()))
+                .unwrap();
+            let tau = table
+                .register_symbol("tau", Context::Custom(UID(0x55)))
+                .unwrap();
+            // 自定义回退链也使用富上下文键
+            table.set_fallback_chain(
+                Context::Domain("math.algebra".to_string()),
+                vec![Context::Domain("math".to_string())],
+            );
+            // 快照必须能序列化带数据的 Context 键
+            table.snapshot(&snap_path).unwrap();
+            (pi, tau)
+        };
+
+        let mut recovered = SymbolTable::recover(&snap_path, &wal_path).unwrap();
+        assert_eq!(
+            recovered.get_uid("pi", &Context::Domain("math".to_string())),
+            Some(pi_uid)
+        );
+        assert_eq!(
+            recovered.get_uid("tau", &Context::Custom(UID(0x55))),
+            Some(tau_uid)
+        );
+
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+    }
+
+    #[test]
+    fn test_snapshot_truncates_wal() {
+        let dir = std::env::temp_dir();
+        let tag = format!("urs_snap_{}", std::process::id());
+        let wal_path = dir.join(format!("{}.wal", tag));
+        let snap_path = dir.join(format!("{}.snap", tag));
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+
+        let mut table = SymbolTable::new();
+        table
+            .enable_persistence(&wal_path, Some(snap_path.clone()), 0, FsyncPolicy::Never)
+            .unwrap();
+        table.register_symbol("a", Context::Global).unwrap();
+        table.register_symbol("b", Context::Global).unwrap();
+
+        table.snapshot(&snap_path).unwrap();
+
+        // 快照后WAL应被截断为空
+        let wal_len = std::fs::metadata(&wal_path).unwrap().len();
+        assert_eq!(wal_len, 0);
+
+        // 快照自身可恢复出相同符号
+        let recovered = SymbolTable::recover(&snap_path, &wal_path).unwrap();
+        assert!(recovered.symbol_to_mapping.contains_key("a"));
+        assert!(recovered.symbol_to_mapping.contains_key("b"));
+
+        let _ = std::fs::remove_file(&wal_path);
+        let _ = std::fs::remove_file(&snap_path);
+    }
+
     #[test]
     fn test_cleanup() {
         let mut table = SymbolTable::new();
@@ -550,4 +1567,25 @@ mod tests {
         
         assert!(removed <= initial_count);
     }
+
+    #[test]
+    fn test_evict_to_capacity() {
+        let mut table = SymbolTable::new();
+        for i in 0..10 {
+            table.register_symbol(&format!("sym_{}", i), Context::Global).unwrap();
+        }
+
+        // 让其中一个成为热点，使其分值最高、最不该被淘汰
+        for _ in 0..50 {
+            table.get_uid("sym_0", &Context::Global);
+        }
+
+        let removed = table.evict_to(5, 60_000.0);
+        assert_eq!(removed, 5);
+        assert_eq!(table.get_stats().total_symbols, 5);
+        assert_eq!(table.get_stats().evictions, 5);
+
+        // 热点符号应当保留
+        assert!(table.get_uid("sym_0", &Context::Global).is_some());
+    }
 }
\ No newline at end of file
@@ -5,10 +5,10 @@
 use crate::core::types::UID;
 use crate::core::symbol_map::{register_global_symbol, get_global_uid};
 
-/// 预定义系统UID
-/// 
-/// 注意：这些是占位符，实际UID在系统初始化时动态生成
-/// 使用对应的getter函数获取实际UID
+// 预定义系统UID
+//
+// 注意：这些是占位符，实际UID在系统初始化时动态生成
+// 使用对应的getter函数获取实际UID
 
 // 关系标记
 pub const REL_ROLE_SYMBOL: &str = "REL_ROLE";
@@ -201,14 +201,21 @@ pub fn initialize_system_uids() -> Result<(), crate::core::symbol_map::SymbolErr
     register_global_symbol(EXEC_MARKER_SYMBOL)?;
     register_global_symbol(SUCCESS_MARKER_SYMBOL)?;
     register_global_symbol(FAILURE_MARKER_SYMBOL)?;
-    
+
+    // 符号就绪后，为内置OP_*操作码预注册空操作桩
+    use crate::core::dispatch::{global_dispatcher, register_builtin_ops};
+    register_builtin_ops(global_dispatcher());
+
     Ok(())
 }
 
 /// UID范围常量（用于验证和过滤）
 pub mod uid_ranges {
     use crate::core::types::UID;
-    
+    use std::sync::Mutex;
+    use lazy_static::lazy_static;
+
+
     /// 系统UID范围起始
     pub const SYSTEM_UID_START: u64 = 0x0000000100000000;
     
@@ -237,6 +244,137 @@ pub mod uid_ranges {
     pub fn generate_temp_uid(counter: u64) -> UID {
         UID(TEMP_UID_START + counter)
     }
+
+    /// 临时UID分配错误
+    #[derive(Debug, thiserror::Error, PartialEq, Eq)]
+    pub enum TempUidError {
+        #[error("临时UID空间已耗尽")]
+        Exhausted,
+    }
+
+    /// 可回收的临时UID分配器
+    ///
+    /// 借鉴内核分配/回收PID的做法：在 `TEMP_UID_START..` 范围上维护一个位图
+    /// 与一个滚动的“下次扫描”游标。`allocate` 从游标向前扫描最低的空闲位，
+    /// `release` 清位且幂等，从而为模式匹配、瞬态关系等场景提供真正的
+    /// 分配/释放生命周期（原始计数器无法回收已释放的UID）。
+    pub struct TempUidAllocator {
+        inner: Mutex<AllocatorState>,
+        capacity: usize,
+    }
+
+    struct AllocatorState {
+        /// 每位表示一个临时UID槽位（1=已占用）
+        bitmap: Vec<u64>,
+        /// 下次扫描的起始下标（滚动）
+        cursor: usize,
+        /// 已占用槽位数
+        used: usize,
+    }
+
+    impl TempUidAllocator {
+        /// 以给定容量创建分配器，管理 `TEMP_UID_START..TEMP_UID_START+capacity`
+        pub fn new(capacity: usize) -> Self {
+            let words = capacity.div_ceil(64);
+            Self {
+                inner: Mutex::new(AllocatorState {
+                    bitmap: vec![0u64; words],
+                    cursor: 0,
+                    used: 0,
+                }),
+                capacity,
+            }
+        }
+
+        /// 分配一个空闲临时UID
+        ///
+        /// 从游标向前环绕扫描最低的空闲位，命中则置位并推进游标；若已满返回
+        /// [`TempUidError::Exhausted`]。
+        pub fn allocate(&self) -> Result<UID, TempUidError> {
+            let mut st = self.inner.lock().unwrap();
+            if st.used >= self.capacity {
+                return Err(TempUidError::Exhausted);
+            }
+
+            let cap = self.capacity;
+            let start = st.cursor;
+            for offset in 0..cap {
+                let idx = (start + offset) % cap;
+                let (word, bit) = (idx / 64, idx % 64);
+                if st.bitmap[word] & (1u64 << bit) == 0 {
+                    st.bitmap[word] |= 1u64 << bit;
+                    st.used += 1;
+                    st.cursor = (idx + 1) % cap;
+                    return Ok(UID(TEMP_UID_START + idx as u64));
+                }
+            }
+
+            Err(TempUidError::Exhausted)
+        }
+
+        /// 释放一个临时UID（幂等）
+        ///
+        /// 先用 [`is_temp_uid`] 与容量校验范围，越界返回 `false`；
+        /// 重复释放同一UID也安全返回 `false`。
+        pub fn release(&self, uid: UID) -> bool {
+            if !is_temp_uid(uid) {
+                return false;
+            }
+            let idx = (uid.0 - TEMP_UID_START) as usize;
+            if idx >= self.capacity {
+                return false;
+            }
+
+            let mut st = self.inner.lock().unwrap();
+            let (word, bit) = (idx / 64, idx % 64);
+            if st.bitmap[word] & (1u64 << bit) != 0 {
+                st.bitmap[word] &= !(1u64 << bit);
+                st.used -= 1;
+                // 把游标回拉到刚释放的槽位，使下一次分配优先复用它
+                st.cursor = st.cursor.min(idx);
+                true
+            } else {
+                false
+            }
+        }
+
+        /// 当前已占用的槽位数
+        pub fn used(&self) -> usize {
+            self.inner.lock().unwrap().used
+        }
+
+        /// 分配器容量
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+    }
+
+    /// 全局临时UID分配器容量（65536个槽位）
+    const TEMP_UID_CAPACITY: usize = 1 << 16;
+
+    lazy_static! {
+        static ref GLOBAL_TEMP_ALLOCATOR: TempUidAllocator = TempUidAllocator::new(TEMP_UID_CAPACITY);
+    }
+
+    /// 获取全局临时UID分配器
+    pub fn global_temp_allocator() -> &'static TempUidAllocator {
+        &GLOBAL_TEMP_ALLOCATOR
+    }
+
+    /// 从全局分配器分配一个临时UID
+    pub fn allocate_temp_uid() -> Result<UID, TempUidError> {
+        GLOBAL_TEMP_ALLOCATOR.allocate()
+    }
+
+    /// 向全局分配器释放一个临时UID
+    pub fn release_temp_uid(uid: UID) -> bool {
+        GLOBAL_TEMP_ALLOCATOR.release(uid)
+    }
+
+    /// 当前仍被占用的临时UID数量
+    pub fn temp_uids_outstanding() -> usize {
+        GLOBAL_TEMP_ALLOCATOR.used()
+    }
 }
 
 #[cfg(test)]
@@ -290,6 +428,36 @@ mod tests {
         assert!(is_temp_uid(temp2));
     }
     
+    #[test]
+    fn test_temp_uid_allocator() {
+        use uid_ranges::*;
+
+        let alloc = TempUidAllocator::new(4);
+
+        let a = alloc.allocate().unwrap();
+        let b = alloc.allocate().unwrap();
+        assert_ne!(a, b);
+        assert!(is_temp_uid(a));
+        assert_eq!(alloc.used(), 2);
+
+        // 释放后应可被重新分配
+        assert!(alloc.release(a));
+        assert_eq!(alloc.used(), 1);
+        // 幂等：重复释放无副作用
+        assert!(!alloc.release(a));
+
+        let c = alloc.allocate().unwrap();
+        assert_eq!(c, a);
+
+        // 填满后报告耗尽
+        alloc.allocate().unwrap();
+        alloc.allocate().unwrap();
+        assert_eq!(alloc.allocate(), Err(TempUidError::Exhausted));
+
+        // 非临时范围的UID无法释放
+        assert!(!alloc.release(UID(SYSTEM_UID_START)));
+    }
+
     #[test]
     fn test_uid_getters() {
         // 初始化
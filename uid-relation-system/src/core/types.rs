@@ -1,10 +1,11 @@
 //! 核心类型定义
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use core::fmt;
+use uid_error_derive::UidError;
 
 /// 64位全局唯一标识符
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UID(pub u64);
 
 impl fmt::Display for UID {
@@ -81,25 +82,35 @@ pub struct LogicOffset {
 
 /// 序列版本号
 /// 每次修改递增
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct SequenceVersion(pub u32);
 
 /// 系统级错误码
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// 每个变体通过 `#[uid(...)]` 绑定一个稳定的UID错误码，由 `UidError` 派生宏在
+/// 编译期生成 `error_uid`/`from_error_uid`/`all_codes`，取代手写的并行映射表。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, UidError)]
 pub enum SystemError {
     /// 未找到指定UID
+    #[uid(0xE000000000000111)]
     UIDNotFound(UID),
     /// 位置无效
+    #[uid(0xE000000000000101)]
     InvalidPosition,
     /// 序列版本不匹配
+    #[uid(0xE000000000000102)]
     VersionMismatch,
     /// 锚点不存在
+    #[uid(0xE000000000000112)]
     AnchorNotFound(UID),
     /// 操作不支持
+    #[uid(0xE000000000000103)]
     OperationNotSupported,
     /// 存储错误
+    #[uid(0xE000000000000104)]
     StorageError,
     /// 解析错误
+    #[uid(0xE000000000000105)]
     ParseError,
 }
 
@@ -115,7 +126,7 @@ pub enum FallbackStrategy {
 }
 
 /// 错误级别
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ErrorLevel {
     /// 致命错误，系统无法继续
     Fatal,
@@ -1,78 +1,638 @@
 //! 错误处理模块
+//!
+//! 该模块不依赖 `std`：在关闭默认 `std` 特性时，整套错误 / UID 序列机制仅凭
+//! `alloc` 即可编译。`std` 特性（默认开启）下额外提供 `From<std::io::Error>`
+//! 转换，保留既有的 `?` 人机工学。
 
-use crate::core::types::{SystemError, ErrorLevel};
-use thiserror::Error;
+use crate::core::types::{SystemError, ErrorLevel, UID};
+use uid_error_derive::UidError;
 
-#[derive(Error, Debug)]
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// I/O 错误种类
+///
+/// `std::io::ErrorKind` 的一个精简、`no_std` 友好的镜像，仅保留存储层会产生的
+/// 少数几种；其余一律归入 [`IoErrorKind::Other`]。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoErrorKind {
+    /// 资源不存在
+    NotFound,
+    /// 权限不足
+    PermissionDenied,
+    /// 数据提前结束
+    UnexpectedEof,
+    /// 操作被中断
+    Interrupted,
+    /// 其它
+    Other,
+}
+
+/// 不依赖 `std` 的 I/O 错误
+///
+/// 取代 `CoreError::StorageError` 原先直接包裹的 `std::io::Error`：携带一个
+/// [`IoErrorKind`] 与可选的描述信息，从而在 `no_std` 下也能表达存储失败。
+#[derive(Debug, Clone)]
+pub struct IoError {
+    /// 错误种类
+    pub kind: IoErrorKind,
+    /// 可选的补充描述
+    pub message: Option<String>,
+}
+
+impl IoError {
+    /// 以种类和描述构造
+    pub fn new(kind: IoErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: Some(message.into()),
+        }
+    }
+
+    /// 仅以种类构造（无描述）
+    pub fn from_kind(kind: IoErrorKind) -> Self {
+        Self {
+            kind,
+            message: None,
+        }
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(msg) => write!(f, "{:?}: {}", self.kind, msg),
+            None => write!(f, "{:?}", self.kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for IoError {
+    fn from(err: std::io::Error) -> Self {
+        use std::io::ErrorKind as K;
+        let kind = match err.kind() {
+            K::NotFound => IoErrorKind::NotFound,
+            K::PermissionDenied => IoErrorKind::PermissionDenied,
+            K::UnexpectedEof => IoErrorKind::UnexpectedEof,
+            K::Interrupted => IoErrorKind::Interrupted,
+            _ => IoErrorKind::Other,
+        };
+        IoError::new(kind, alloc::format!("{}", err))
+    }
+}
+
+/// `CoreError::Contextual` 的堆上载荷
+///
+/// 把底层错误与上下文面包屑一起放到堆上，使 `Contextual` 变体在枚举里只占一个
+/// 指针宽度（见下方对 `size_of::<CoreError>()` 的约束）。
+#[derive(Debug)]
+pub struct ContextChain {
+    /// 被包裹的底层错误
+    pub source: CoreError,
+    /// 按包裹先后累积的上下文UID
+    pub context: Vec<UID>,
+}
+
+/// 核心错误类型
+///
+/// 仿照标准库 `std::io::Error` 的打包思路：便宜的变体（仅含 `UID`、版本对）直接
+/// 内联，而携带字符串 / I/O / 上下文链的“富”变体一律藏在一个**瘦指针** `Box`
+/// 之后。于是整个枚举被压到两个机器字（`size_of::<CoreError>() == 2 * usize`），
+/// 热路径上传递错误不再拖着一个大结构体。
+#[derive(Debug, UidError)]
 pub enum CoreError {
-    #[error("UID未找到: {0:?}")]
-    UIDNotFound(crate::core::types::UID),
-    
-    #[error("位置无效: {0}")]
-    InvalidPosition(String),
-    
-    #[error("序列版本不匹配: 期望{expected}, 实际{actual}")]
+    #[uid(0xE000000000000211)]
+    UIDNotFound(UID),
+
+    // 字符串藏在瘦指针之后，变体本身只占一个字
+    #[uid(0xE000000000000201)]
+    InvalidPosition(Box<String>),
+
+    #[uid(0xE000000000000202)]
     VersionMismatch {
         expected: crate::core::types::SequenceVersion,
         actual: crate::core::types::SequenceVersion,
     },
-    
-    #[error("锚点未找到: {0:?}")]
-    AnchorNotFound(crate::core::types::UID),
-    
-    #[error("存储错误: {0}")]
-    StorageError(#[from] std::io::Error),
-    
-    #[error("解析错误: {0}")]
-    ParseError(String),
-    
-    #[error("系统错误: {0:?}")]
-    SystemError(SystemError),
+
+    #[uid(0xE000000000000212)]
+    AnchorNotFound(UID),
+
+    // 载荷无 `Default`，无法仅凭错误码重建，故标注 opaque
+    #[uid(0xE000000000000204, opaque)]
+    StorageError(Box<IoError>),
+
+    #[uid(0xE000000000000205)]
+    ParseError(Box<String>),
+
+    // 载荷是另一错误枚举，无 `Default`，同样不可逆向重建
+    #[uid(0xE000000000000206, opaque)]
+    SystemError(Box<SystemError>),
+
+    /// 携带上下文面包屑的包裹错误
+    ///
+    /// `.context(...)` / `.with_context(...)` 在不丢失底层 `source` 的前提下，
+    /// 把一串上下文UID挂到错误上，使完整因果链可经 [`core::error::Error::source`]
+    /// 遍历。载荷整体 `Box` 化，无法仅凭码重建，故 opaque。
+    #[uid(0xE000000000000207, opaque)]
+    Contextual(Box<ContextChain>),
+}
+
+impl CoreError {
+    /// 构造一个解析错误（把描述装进瘦指针）
+    pub fn parse_error(msg: impl Into<String>) -> CoreError {
+        CoreError::ParseError(Box::new(msg.into()))
+    }
+
+    /// 构造一个“位置无效”错误
+    pub fn invalid_position(msg: impl Into<String>) -> CoreError {
+        CoreError::InvalidPosition(Box::new(msg.into()))
+    }
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::UIDNotFound(uid) => write!(f, "UID未找到: {:?}", uid),
+            CoreError::InvalidPosition(s) => write!(f, "位置无效: {}", s),
+            CoreError::VersionMismatch { expected, actual } => write!(
+                f,
+                "序列版本不匹配: 期望{}, 实际{}",
+                expected.0, actual.0
+            ),
+            CoreError::AnchorNotFound(uid) => write!(f, "锚点未找到: {:?}", uid),
+            CoreError::StorageError(e) => write!(f, "存储错误: {}", e),
+            CoreError::ParseError(s) => write!(f, "解析错误: {}", s),
+            CoreError::SystemError(e) => write!(f, "系统错误: {:?}", e),
+            CoreError::Contextual(c) => {
+                write!(f, "{} (上下文: {:?})", c.source, c.context)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CoreError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CoreError::Contextual(c) => Some(&c.source),
+            _ => None,
+        }
+    }
+}
+
+/// 为 `Result<T, CoreError>` 提供上下文包裹的扩展特征（anyhow 风格）
+pub trait Contextual<T> {
+    /// 附加一个上下文UID
+    fn context(self, ctx: impl Into<UID>) -> Result<T, CoreError>;
+    /// 惰性附加一个上下文UID（仅在出错时求值）
+    fn with_context<F: FnOnce() -> UID>(self, f: F) -> Result<T, CoreError>;
+}
+
+impl<T> Contextual<T> for Result<T, CoreError> {
+    fn context(self, ctx: impl Into<UID>) -> Result<T, CoreError> {
+        self.map_err(|e| e.push_context(ctx.into()))
+    }
+
+    fn with_context<F: FnOnce() -> UID>(self, f: F) -> Result<T, CoreError> {
+        self.map_err(|e| e.push_context(f()))
+    }
+}
+
+impl CoreError {
+    /// 把一个上下文UID压入链（已是 `Contextual` 则追加，否则包裹）
+    fn push_context(self, ctx: UID) -> CoreError {
+        match self {
+            CoreError::Contextual(mut c) => {
+                c.context.push(ctx);
+                CoreError::Contextual(c)
+            }
+            other => CoreError::Contextual(Box::new(ContextChain {
+                source: other,
+                context: alloc::vec![ctx],
+            })),
+        }
+    }
+
+    /// 展开 `Contextual` 链，收集全部上下文面包屑（按包裹先后），返回最内层错误
+    fn unwind_context(&self, acc: &mut Vec<UID>) -> &CoreError {
+        let mut err = self;
+        while let CoreError::Contextual(c) = err {
+            acc.extend(c.context.iter().copied());
+            err = &c.source;
+        }
+        err
+    }
+
+    /// 将本错误（含底层成因）映射为对应的 [`SystemError`] 错误码变体
+    fn as_system_error(&self) -> SystemError {
+        match self {
+            CoreError::UIDNotFound(uid) => SystemError::UIDNotFound(*uid),
+            CoreError::InvalidPosition(_) => SystemError::InvalidPosition,
+            CoreError::VersionMismatch { .. } => SystemError::VersionMismatch,
+            CoreError::AnchorNotFound(uid) => SystemError::AnchorNotFound(*uid),
+            CoreError::StorageError(_) => SystemError::StorageError,
+            CoreError::ParseError(_) => SystemError::ParseError,
+            CoreError::SystemError(e) => **e,
+            // 展开后通常不会再遇到 Contextual；稳妥起见递归到底层成因
+            CoreError::Contextual(c) => c.source.as_system_error(),
+        }
+    }
 }
 
 impl From<SystemError> for CoreError {
     fn from(err: SystemError) -> Self {
-        CoreError::SystemError(err)
+        CoreError::SystemError(Box::new(err))
+    }
+}
+
+impl From<IoError> for CoreError {
+    fn from(err: IoError) -> Self {
+        CoreError::StorageError(Box::new(err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for CoreError {
+    fn from(err: std::io::Error) -> Self {
+        CoreError::StorageError(Box::new(IoError::from(err)))
+    }
+}
+
+/// 错误序列的魔数头
+const ERROR_START: UID = UID(0xE000000000000001);
+
+/// 将错误级别编码为UID
+fn level_to_uid(level: ErrorLevel) -> UID {
+    match level {
+        ErrorLevel::Fatal => UID(0xE000000000000002),
+        ErrorLevel::Severe => UID(0xE000000000000003),
+        ErrorLevel::Warning => UID(0xE000000000000004),
+        ErrorLevel::Info => UID(0xE000000000000005),
+    }
+}
+
+/// 取出带载荷错误码所携带的UID（无载荷变体返回 `None`）
+///
+/// 稳定码只标识变体，`UIDNotFound` / `AnchorNotFound` 真正指向的UID必须另行随行，
+/// 否则编码即丢失信息。
+fn payload_of(code: SystemError) -> Option<UID> {
+    match code {
+        SystemError::UIDNotFound(uid) | SystemError::AnchorNotFound(uid) => Some(uid),
+        _ => None,
+    }
+}
+
+/// 把随行的载荷UID注回 `from_error_uid` 得到的占位变体
+fn with_payload(code: SystemError, payload: UID) -> SystemError {
+    match code {
+        SystemError::UIDNotFound(_) => SystemError::UIDNotFound(payload),
+        SystemError::AnchorNotFound(_) => SystemError::AnchorNotFound(payload),
+        other => other,
+    }
+}
+
+/// 将UID解码回错误级别（未知码返回 `None`）
+fn level_from_uid(uid: UID) -> Option<ErrorLevel> {
+    match uid {
+        UID(0xE000000000000002) => Some(ErrorLevel::Fatal),
+        UID(0xE000000000000003) => Some(ErrorLevel::Severe),
+        UID(0xE000000000000004) => Some(ErrorLevel::Warning),
+        UID(0xE000000000000005) => Some(ErrorLevel::Info),
+        _ => None,
     }
 }
 
-/// 生成错误序列
 pub fn make_error_sequence(
     level: ErrorLevel,
     error_code: SystemError,
     context: Vec<crate::core::types::UID>,
     details: Vec<crate::core::types::UID>,
 ) -> Vec<crate::core::types::UID> {
-    use crate::core::types::UID;
-    
-    // 错误序列格式: [ERROR_START, level, error_code, context_len, context..., details...]
     let mut result = Vec::new();
-    
-    // 这些UID将在后续步骤中定义
-    result.push(UID(0xE000000000000001)); // ERROR_START
-    result.push(match level {
-        ErrorLevel::Fatal => UID(0xE000000000000002),
-        ErrorLevel::Severe => UID(0xE000000000000003),
-        ErrorLevel::Warning => UID(0xE000000000000004),
-        ErrorLevel::Info => UID(0xE000000000000005),
-    });
-    
-    // 错误码转换
-    let error_uid = match error_code {
-        SystemError::UIDNotFound(uid) => uid,
-        SystemError::InvalidPosition => UID(0xE000000000000101),
-        SystemError::VersionMismatch => UID(0xE000000000000102),
-        SystemError::AnchorNotFound(uid) => uid,
-        SystemError::OperationNotSupported => UID(0xE000000000000103),
-        SystemError::StorageError => UID(0xE000000000000104),
-        SystemError::ParseError => UID(0xE000000000000105),
-    };
-    result.push(error_uid);
-    
-    // 上下文长度和内容
-    result.push(UID(context.len() as u64));
-    result.extend(context);
-    result.extend(details);
-    
+    make_error_sequence_into(&mut result, level, error_code, &context, &details);
     result
+}
+
+/// 将错误序列追加进调用方提供的缓冲区
+///
+/// 与 [`make_error_sequence`] 等价，但不自行分配 `Vec`：热路径（例如序列引擎里
+/// 逐操作的校验）可复用同一个缓冲区，避免每次都新分配。
+pub fn make_error_sequence_into(
+    out: &mut Vec<UID>,
+    level: ErrorLevel,
+    error_code: SystemError,
+    context: &[UID],
+    details: &[UID],
+) {
+    // 错误序列格式: [ERROR_START, level, error_code, context_len, context..., payload?, details...]
+    out.push(ERROR_START);
+    out.push(level_to_uid(level));
+
+    // 错误码由派生宏生成的稳定映射给出，取代手写的并行 match 表
+    out.push(error_code.error_uid());
+
+    // 上下文长度和内容
+    out.push(UID(context.len() as u64));
+    out.extend_from_slice(context);
+
+    // 带载荷的错误码把其UID作为首个细节写入，使稳定码不丢失具体目标
+    if let Some(payload) = payload_of(error_code) {
+        out.push(payload);
+    }
+    out.extend_from_slice(details);
+}
+
+/// 生成错误序列并自动摊平 `Contextual` 链
+///
+/// 沿 `source` 链展开 [`CoreError::Contextual`]，把沿途的上下文UID作为面包屑填入
+/// `context` 字段，错误码取最内层成因对应的 [`SystemError`]。这样深层嵌套的失败
+/// （例如在 `InvalidPosition` 处施加操作时又遇到 `AnchorNotFound`）也能带着完整
+/// 的UID轨迹序列化，而不是只剩最内层错误。
+pub fn make_error_sequence_from(
+    level: ErrorLevel,
+    error: &CoreError,
+    details: Vec<UID>,
+) -> Vec<UID> {
+    let mut context = Vec::new();
+    let innermost = error.unwind_context(&mut context);
+    make_error_sequence(level, innermost.as_system_error(), context, details)
+}
+
+/// 从错误序列解码出的结构化错误
+///
+/// 是 [`make_error_sequence`] 的逆：把线格式还原为级别、错误码与上下文 /
+/// 细节分段，使编码后的错误成为可往返的线协议。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedError {
+    /// 错误级别
+    pub level: ErrorLevel,
+    /// 经稳定码反解出的系统错误（有载荷者的UID已由随行细节注回）
+    pub code: SystemError,
+    /// 上下文UID分段
+    pub context: Vec<UID>,
+    /// 细节UID分段
+    pub details: Vec<UID>,
+}
+
+/// 将一段错误序列解析回 [`ParsedError`]
+///
+/// 校验 [`ERROR_START`] 魔数、级别码与错误码，并按 `context_len` 切分剩余部分。
+/// 任一校验失败都返回带出错下标的 [`CoreError::ParseError`]。
+pub fn parse_error_sequence(seq: &[UID]) -> Result<ParsedError, CoreError> {
+    if seq.len() < 4 {
+        return Err(CoreError::parse_error(alloc::format!(
+            "偏移{}: 错误序列被截断: 仅 {} 个UID，至少需要4个前导UID",
+            seq.len(),
+            seq.len()
+        )));
+    }
+
+    if seq[0] != ERROR_START {
+        return Err(CoreError::parse_error(alloc::format!(
+            "偏移0: 非法魔数头 {:?}，期望 {:?}",
+            seq[0],
+            ERROR_START
+        )));
+    }
+
+    let level = level_from_uid(seq[1]).ok_or_else(|| {
+        CoreError::parse_error(alloc::format!("偏移1: 未知的错误级别码 {:?}", seq[1]))
+    })?;
+
+    let code = SystemError::from_error_uid(seq[2]).ok_or_else(|| {
+        CoreError::parse_error(alloc::format!("偏移2: 未知的错误码 {:?}", seq[2]))
+    })?;
+
+    let context_len = seq[3].0 as usize;
+    let remaining = &seq[4..];
+    if context_len > remaining.len() {
+        return Err(CoreError::parse_error(alloc::format!(
+            "偏移3: context_len={} 超过剩余 {} 个UID",
+            context_len,
+            remaining.len()
+        )));
+    }
+
+    let (context, rest) = remaining.split_at(context_len);
+
+    // 带载荷的错误码在上下文之后紧跟其载荷UID：取出并注回，其余才是用户细节
+    let (code, details) = if payload_of(code).is_some() {
+        let payload = rest.first().ok_or_else(|| {
+            CoreError::parse_error(alloc::format!(
+                "偏移{}: 错误码 {:?} 需要随行载荷UID，但细节段为空",
+                4 + context_len,
+                seq[2]
+            ))
+        })?;
+        (with_payload(code, *payload), rest[1..].to_vec())
+    } else {
+        (code, rest.to_vec())
+    };
+
+    Ok(ParsedError {
+        level,
+        code,
+        context: context.to_vec(),
+        details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_error_codes_roundtrip() {
+        // 每个变体的码都能反解回同一变体
+        for code in SystemError::all_codes() {
+            let err = SystemError::from_error_uid(*code)
+                .expect("已分配的码必须可反解");
+            assert_eq!(err.error_uid(), *code);
+        }
+        // 未分配的码返回 None
+        assert!(SystemError::from_error_uid(UID(0x1234)).is_none());
+    }
+
+    #[test]
+    fn test_error_codes_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for code in SystemError::all_codes() {
+            assert!(seen.insert(*code), "错误码重复: {:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_make_error_sequence_uses_stable_code() {
+        let seq = make_error_sequence(
+            ErrorLevel::Severe,
+            SystemError::InvalidPosition,
+            vec![],
+            vec![],
+        );
+        assert_eq!(seq[0], UID(0xE000000000000001));
+        assert_eq!(seq[1], UID(0xE000000000000003));
+        assert_eq!(seq[2], SystemError::InvalidPosition.error_uid());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_error_kind_mapping() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "缺失文件");
+        let mapped = IoError::from(io);
+        assert_eq!(mapped.kind, IoErrorKind::NotFound);
+        assert!(mapped.message.is_some());
+
+        // 经 `?` 转换为 CoreError
+        let err: CoreError =
+            std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+        match err {
+            CoreError::StorageError(e) => assert_eq!(e.kind, IoErrorKind::PermissionDenied),
+            other => panic!("期望 StorageError, 实得 {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_sequence_roundtrip() {
+        let ctx = vec![UID(0xAA), UID(0xBB)];
+        let det = vec![UID(0xCC)];
+        let seq = make_error_sequence(
+            ErrorLevel::Warning,
+            SystemError::InvalidPosition,
+            ctx.clone(),
+            det.clone(),
+        );
+        let parsed = parse_error_sequence(&seq).unwrap();
+        assert_eq!(parsed.level, ErrorLevel::Warning);
+        assert_eq!(parsed.code, SystemError::InvalidPosition);
+        assert_eq!(parsed.context, ctx);
+        assert_eq!(parsed.details, det);
+    }
+
+    #[test]
+    fn test_payload_code_roundtrip() {
+        // 带载荷的错误码：UID 必须完整往返，且稳定码仍可识别变体
+        let det = vec![UID(0xCC)];
+        let seq = make_error_sequence(
+            ErrorLevel::Fatal,
+            SystemError::UIDNotFound(UID(0x42)),
+            vec![UID(0xAA)],
+            det.clone(),
+        );
+        // 槽2仍是稳定码，载荷作为上下文之后的首个细节随行
+        assert_eq!(seq[2], SystemError::UIDNotFound(UID(0)).error_uid());
+        let parsed = parse_error_sequence(&seq).unwrap();
+        assert_eq!(parsed.code, SystemError::UIDNotFound(UID(0x42)));
+        assert_eq!(parsed.context, vec![UID(0xAA)]);
+        assert_eq!(parsed.details, det);
+    }
+
+    #[test]
+    fn test_payload_code_missing_payload_rejected() {
+        // 稳定码声称有载荷，但细节段为空 → 报错而非静默造出默认UID
+        let seq = [
+            ERROR_START,
+            level_to_uid(ErrorLevel::Fatal),
+            SystemError::AnchorNotFound(UID(0)).error_uid(),
+            UID(0),
+        ];
+        assert!(matches!(
+            parse_error_sequence(&seq),
+            Err(CoreError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_sequence_rejections() {
+        // 截断：少于4个前导UID
+        assert!(matches!(
+            parse_error_sequence(&[ERROR_START, UID(0xE000000000000002)]),
+            Err(CoreError::ParseError(_))
+        ));
+        // 非法魔数头
+        assert!(matches!(
+            parse_error_sequence(&[UID(0x1), UID(0xE000000000000002), UID(0xE000000000000101), UID(0)]),
+            Err(CoreError::ParseError(_))
+        ));
+        // 未知级别码
+        assert!(matches!(
+            parse_error_sequence(&[ERROR_START, UID(0xDEAD), UID(0xE000000000000101), UID(0)]),
+            Err(CoreError::ParseError(_))
+        ));
+        // 未知错误码
+        assert!(matches!(
+            parse_error_sequence(&[ERROR_START, UID(0xE000000000000002), UID(0xDEAD), UID(0)]),
+            Err(CoreError::ParseError(_))
+        ));
+        // context_len 超过剩余长度
+        assert!(matches!(
+            parse_error_sequence(&[ERROR_START, UID(0xE000000000000002), UID(0xE000000000000101), UID(5)]),
+            Err(CoreError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_context_chain_and_source() {
+        use core::error::Error;
+
+        let base: Result<(), CoreError> = Err(CoreError::AnchorNotFound(UID(0x7)));
+        let wrapped = base
+            .context(UID(0x100))
+            .with_context(|| UID(0x200))
+            .unwrap_err();
+
+        // source() 可走回底层错误
+        let src = wrapped.source().expect("Contextual 应有 source");
+        assert_eq!(src.to_string(), CoreError::AnchorNotFound(UID(0x7)).to_string());
+
+        // 摊平后面包屑按包裹先后顺序出现在 context 字段
+        let seq = make_error_sequence_from(ErrorLevel::Severe, &wrapped, vec![]);
+        let parsed = parse_error_sequence(&seq).unwrap();
+        // 载荷UID 0x7 随行保留，而非丢成默认占位
+        assert_eq!(parsed.code, SystemError::AnchorNotFound(UID(0x7)));
+        assert_eq!(parsed.context, vec![UID(0x100), UID(0x200)]);
+    }
+
+    #[test]
+    fn test_core_error_is_two_words() {
+        // 富变体全部藏在瘦指针之后，枚举被压到两个机器字
+        assert_eq!(
+            core::mem::size_of::<CoreError>(),
+            2 * core::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_make_error_sequence_into_reuses_buffer() {
+        let mut buf = Vec::new();
+        make_error_sequence_into(
+            &mut buf,
+            ErrorLevel::Info,
+            SystemError::StorageError,
+            &[UID(1)],
+            &[],
+        );
+        let first_len = buf.len();
+        // 复用同一缓冲区继续追加（容量不足时 Vec 仍会扩容）
+        make_error_sequence_into(
+            &mut buf,
+            ErrorLevel::Fatal,
+            SystemError::ParseError,
+            &[],
+            &[UID(9)],
+        );
+        assert_eq!(buf.len(), first_len + 5);
+        // 与一次性分配版本语义一致
+        let oneshot = make_error_sequence(ErrorLevel::Info, SystemError::StorageError, vec![UID(1)], vec![]);
+        assert_eq!(&buf[..first_len], &oneshot[..]);
+    }
+
+    #[test]
+    fn test_core_error_opaque_not_reconstructed() {
+        // opaque 变体仍分配了码，但不可反解
+        let storage_code = UID(0xE000000000000204);
+        assert!(CoreError::all_codes().contains(&storage_code));
+        assert!(CoreError::from_error_uid(storage_code).is_none());
+    }
 }
\ No newline at end of file
@@ -6,6 +6,12 @@
 //! 3. 位置基于锚点和关系
 //! 4. 序列是逻辑环形的
 
+// 错误子系统可在 `no_std` 下编译（仅依赖 `alloc`）。其余模块仍需 `std`，因此
+// `no_std` 仅在关闭默认的 `std` 特性时生效。
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod core;
 pub mod error;
 
@@ -14,12 +20,16 @@ pub use crate::core::types::{
     UID, Direction, RelationalPosition, LogicOffset, 
     SequenceVersion, SystemError, FallbackStrategy, ErrorLevel
 };
-pub use crate::error::{CoreError, make_error_sequence};
+pub use crate::error::{
+    CoreError, Contextual, make_error_sequence, make_error_sequence_from,
+    make_error_sequence_into, parse_error_sequence, ParsedError,
+};
 
 /// 系统版本信息
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// 初始化日志系统
+#[cfg(feature = "std")]
 pub fn init_logger() {
     env_logger::init();
 }
@@ -27,18 +37,20 @@ pub fn init_logger() {
 // 在src/lib.rs中添加：
 
 /// 初始化整个系统
+#[cfg(feature = "std")]
 pub fn initialize() -> Result<(), Box<dyn std::error::Error>> {
     // 初始化日志
     init_logger();
-    
+
     // 初始化核心系统
     core::initialize_core_system()?;
-    
+
     log::info!("UID Relation System v{} initialized", VERSION);
     Ok(())
 }
 
 /// 获取系统状态
+#[cfg(feature = "std")]
 pub fn system_status() -> core::SystemInfo {
     core::system_info()
 }
\ No newline at end of file
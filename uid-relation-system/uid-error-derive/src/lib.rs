@@ -0,0 +1,163 @@
+//! `#[derive(UidError)]` 派生宏
+//!
+//! 借鉴 errno-derive 的模式：读取每个变体上的 `#[uid(0xE000000000000101)]` 属性，
+//! 为枚举生成稳定的 UID 错误码映射。生成的实现包含：
+//!
+//! - `error_uid(&self) -> UID`：变体 -> 稳定错误码
+//! - `from_error_uid(UID) -> Option<Self>`：错误码 -> 变体（有字段的变体用
+//!   `Default` 填充占位，载荷由上层从错误序列补齐）
+//! - `all_codes() -> &'static [UID]`：全部已分配码，供测试核对
+//!
+//! 宏在展开期拒绝重复码与缺失属性，从而在编译期保证码空间唯一且可逆。
+//!
+//! 个别变体的载荷无法凭错误码重建（例如 `StorageError(Box<IoError>)` 没有
+//! `Default`），可标注 `#[uid(0x..., opaque)]`：这类变体仍参与 `error_uid`
+//! 与 `all_codes`，但 `from_error_uid` 对其返回 `None`。
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Data, DeriveInput, Fields, LitInt, Token,
+};
+
+#[proc_macro_derive(UidError, attributes(uid))]
+pub fn derive_uid_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let data = match &input.data {
+        Data::Enum(e) => e,
+        _ => {
+            return syn::Error::new_spanned(&input, "UidError 只能用于枚举")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    let mut to_uid_arms = Vec::new();
+    let mut from_uid_arms = Vec::new();
+    let mut codes = Vec::new();
+
+    for variant in &data.variants {
+        let vname = &variant.ident;
+
+        let (code, opaque) = match extract_uid(&variant.attrs) {
+            Ok(Some(spec)) => (spec.code, spec.opaque),
+            Ok(None) => {
+                return syn::Error::new_spanned(
+                    variant,
+                    format!("变体 {} 缺少 #[uid(...)] 属性", vname),
+                )
+                .to_compile_error()
+                .into()
+            }
+            Err(e) => return e.to_compile_error().into(),
+        };
+
+        if !seen.insert(code) {
+            return syn::Error::new_spanned(
+                variant,
+                format!("重复的UID错误码: {:#018x}", code),
+            )
+            .to_compile_error()
+            .into();
+        }
+        codes.push(code);
+
+        // 变体 -> 码：忽略字段
+        let match_pattern = match &variant.fields {
+            Fields::Unit => quote! { #name::#vname },
+            Fields::Unnamed(_) => quote! { #name::#vname(..) },
+            Fields::Named(_) => quote! { #name::#vname { .. } },
+        };
+        to_uid_arms.push(quote! { #match_pattern => UID(#code), });
+
+        // 码 -> 变体：有字段者用 Default 占位；opaque 变体无法重建，跳过
+        if !opaque {
+            let construct = match &variant.fields {
+                Fields::Unit => quote! { #name::#vname },
+                Fields::Unnamed(fields) => {
+                    let defaults = fields
+                        .unnamed
+                        .iter()
+                        .map(|_| quote! { ::core::default::Default::default() });
+                    quote! { #name::#vname( #(#defaults),* ) }
+                }
+                Fields::Named(fields) => {
+                    let defaults = fields.named.iter().map(|f| {
+                        let ident = f.ident.as_ref().unwrap();
+                        quote! { #ident: ::core::default::Default::default() }
+                    });
+                    quote! { #name::#vname { #(#defaults),* } }
+                }
+            };
+            from_uid_arms.push(quote! { #code => Some(#construct), });
+        }
+    }
+
+    let code_count = codes.len();
+    let code_lits = codes.iter().map(|c| quote! { UID(#c) });
+
+    let expanded = quote! {
+        impl #name {
+            /// 返回该变体的稳定UID错误码
+            pub fn error_uid(&self) -> UID {
+                match self {
+                    #(#to_uid_arms)*
+                }
+            }
+
+            /// 由错误码反解出变体（有字段者载荷为默认占位）
+            pub fn from_error_uid(uid: UID) -> Option<Self> {
+                match uid.0 {
+                    #(#from_uid_arms)*
+                    _ => None,
+                }
+            }
+
+            /// 全部已分配错误码
+            pub fn all_codes() -> &'static [UID] {
+                const CODES: [UID; #code_count] = [ #(#code_lits),* ];
+                &CODES
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// 单个 `#[uid(...)]` 属性的解析结果
+struct UidSpec {
+    code: u64,
+    opaque: bool,
+}
+
+impl Parse for UidSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit: LitInt = input.parse()?;
+        let code = lit.base10_parse::<u64>()?;
+        let mut opaque = false;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let flag: syn::Ident = input.parse()?;
+            if flag == "opaque" {
+                opaque = true;
+            } else {
+                return Err(syn::Error::new_spanned(flag, "未知的 #[uid] 选项"));
+            }
+        }
+        Ok(UidSpec { code, opaque })
+    }
+}
+
+/// 从变体属性中提取 `#[uid(0x..., [opaque])]`
+fn extract_uid(attrs: &[syn::Attribute]) -> syn::Result<Option<UidSpec>> {
+    for attr in attrs {
+        if attr.path().is_ident("uid") {
+            return Ok(Some(attr.parse_args::<UidSpec>()?));
+        }
+    }
+    Ok(None)
+}